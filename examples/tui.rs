@@ -1,6 +1,14 @@
 // Simple text-based user interface
 
-use slot_machine::game::Game;
+use slot_machine::config::paytable::{Combination, Rule};
+use slot_machine::engine::bonus::GameMode;
+use slot_machine::engine::window::Window;
+use slot_machine::engine::NUM_ROWS;
+use slot_machine::io::save::SaveFormat;
+use slot_machine::prelude::*;
+use slot_machine::stats::diagnostics::compare;
+use std::env;
+use std::io::ErrorKind;
 use std::thread::sleep;
 use std::time::Duration;
 
@@ -8,15 +16,214 @@ const BALANCE: u32 = 1000;
 const BET_SIZE: u32 = 1;
 const BET_MIN: u32 = 1;
 const BET_MAX: u32 = 10;
+// The game is saved here after every command and offered to resume on the next launch
+const SAVE_FILE: &str = "slot_machine_tui.json";
 
-fn main() {
-    println!("Greetings!");
+// Metadata of a command, the help and the parser are generated from it
+struct Command {
+    // The first word of the command
+    name: &'static str,
+    usage: &'static str,
+    // Short names of the command, matched before prefixes
+    aliases: &'static [&'static str],
+    // Key of the description in `MESSAGES`
+    description: &'static str,
+}
 
-    println!("Your balance: {} credits", BALANCE);
-    println!("Bet size: {}", BET_SIZE);
-    print_help();
+// Language of the messages, picked by the `LANG` environment variable
+#[derive(Clone, Copy)]
+enum Locale {
+    En,
+    Ru,
+}
 
-    let mut game = Game::new(BALANCE, BET_SIZE, BET_MIN, BET_MAX).unwrap();
+impl Locale {
+    fn from_env() -> Self {
+        match env::var("LANG") {
+            Ok(lang) if lang.starts_with("ru") => Locale::Ru,
+            _ => Locale::En,
+        }
+    }
+
+    // Returns the message with the `key`, the key itself if the catalog misses it
+    fn message(self, key: &'static str) -> &'static str {
+        MESSAGES
+            .iter()
+            .find(|x| x.0 == key)
+            .map_or(key, |&(_, en, ru)| match self {
+                Locale::En => en,
+                Locale::Ru => ru,
+            })
+    }
+}
+
+// Message catalog: the key, the English and the Russian text
+const MESSAGES: &[(&str, &str, &str)] = &[
+    ("greetings", "Greetings!", "Добро пожаловать!"),
+    (
+        "balance",
+        "Your balance: {} credits",
+        "Ваш баланс: {} кредитов",
+    ),
+    ("bet_size", "Bet size: {}", "Размер ставки: {}"),
+    (
+        "resume",
+        "Found a saved game: balance {balance} credits, bet {bet}. Resume it? [Y/n]",
+        "Найдена сохранённая игра: баланс {balance} кредитов, ставка {bet}. Продолжить её? [Y/n]",
+    ),
+    (
+        "help",
+        "To {description}, put {usage}.",
+        "Чтобы {description}, введите {usage}.",
+    ),
+    ("or", " or ", " или "),
+    ("help.balance", "get a balance", "узнать баланс"),
+    ("help.bet", "get a bet size", "узнать размер ставки"),
+    (
+        "help.bet_plus",
+        "increase the size of the bet",
+        "увеличить ставку",
+    ),
+    (
+        "help.bet_minus",
+        "decrease the size of the bet",
+        "уменьшить ставку",
+    ),
+    ("help.spin", "spin the reels", "вращать барабаны"),
+    (
+        "help.autospin",
+        "activate auto-spin for NUMBER spins",
+        "включить автоигру на NUMBER вращений",
+    ),
+    (
+        "help.deposit",
+        "add NUMBER credits to the balance",
+        "добавить NUMBER кредитов на баланс",
+    ),
+    (
+        "help.cashout",
+        "withdraw the whole balance",
+        "вывести весь баланс",
+    ),
+    (
+        "help.payouts",
+        "browse the rules of the paytable with example boards",
+        "листать правила таблицы выплат с примерами",
+    ),
+    (
+        "paytable.navigate",
+        "Enter or n: next, p: previous, arrows then Enter also work, q: back to the game",
+        "Enter или n: дальше, p: назад, стрелки с Enter тоже работают, q: вернуться к игре",
+    ),
+    (
+        "help.history",
+        "show the last 10 spins",
+        "показать последние 10 вращений",
+    ),
+    (
+        "help.diagnose",
+        "compare symbol frequencies with reel weights over NUMBER spins",
+        "сравнить частоты символов с весами барабанов за NUMBER вращений",
+    ),
+    ("help.help", "show this help", "показать эту справку"),
+];
+
+const COMMANDS: &[Command] = &[
+    Command {
+        name: "balance",
+        usage: "balance",
+        aliases: &["b"],
+        description: "help.balance",
+    },
+    Command {
+        name: "bet",
+        usage: "bet",
+        aliases: &[],
+        description: "help.bet",
+    },
+    Command {
+        name: "bet",
+        usage: "bet plus",
+        aliases: &[],
+        description: "help.bet_plus",
+    },
+    Command {
+        name: "bet",
+        usage: "bet minus",
+        aliases: &[],
+        description: "help.bet_minus",
+    },
+    Command {
+        name: "spin",
+        usage: "spin",
+        aliases: &["s"],
+        description: "help.spin",
+    },
+    Command {
+        name: "autospin",
+        usage: "autospin <NUMBER>",
+        aliases: &[],
+        description: "help.autospin",
+    },
+    Command {
+        name: "deposit",
+        usage: "deposit <NUMBER>",
+        aliases: &[],
+        description: "help.deposit",
+    },
+    Command {
+        name: "cashout",
+        usage: "cashout",
+        aliases: &[],
+        description: "help.cashout",
+    },
+    Command {
+        name: "payouts",
+        usage: "payouts",
+        aliases: &[],
+        description: "help.payouts",
+    },
+    Command {
+        name: "history",
+        usage: "history",
+        aliases: &[],
+        description: "help.history",
+    },
+    Command {
+        name: "diagnose",
+        usage: "diagnose <NUMBER>",
+        aliases: &[],
+        description: "help.diagnose",
+    },
+    Command {
+        name: "help",
+        usage: "help",
+        aliases: &[],
+        description: "help.help",
+    },
+];
+
+fn main() {
+    let locale = Locale::from_env();
+    println!("{}", locale.message("greetings"));
+    let mut game = start(locale);
+
+    println!(
+        "{}",
+        locale
+            .message("balance")
+            .replace("{}", &game.credits().to_string())
+    );
+    println!(
+        "{}",
+        locale
+            .message("bet_size")
+            .replace("{}", &game.bet().to_string())
+    );
+    print_help(locale);
+    for conflict in conflicts() {
+        println!("Warning: {}", conflict);
+    }
 
     loop {
         let mut command = String::new();
@@ -25,53 +232,230 @@ fn main() {
             .read_line(&mut command)
             .expect("Failed to read command!");
 
-        match command.trim().to_uppercase().as_str() {
-            "BALANCE" => println!("Your balance: {} credits.", game.credits()),
-            "BET" => println!("Current bet: {} credits.", game.bet()),
-            "BET PLUS" => match bet_plus(&mut game) {
+        let command = command.trim().to_lowercase();
+        let mut words = command.split_whitespace();
+        let name = match words.next().map(resolve) {
+            Some(Ok(name)) => name,
+            Some(Err(e)) => {
+                println!("{}", e);
+                continue;
+            }
+            None => {
+                println!("Invalid command!");
+                continue;
+            }
+        };
+        let args: Vec<&str> = words.collect();
+
+        match (name, args.as_slice()) {
+            ("balance", []) => println!("Your balance: {} credits.", game.credits()),
+            ("bet", []) => println!("Current bet: {} credits.", game.bet()),
+            ("bet", ["plus"]) => match bet_plus(&mut game) {
                 Ok(val) => println!("Bet size: {}.", val),
                 Err(e) => println!("{}", e),
             },
-            "BET MINUS" => match bet_minus(&mut game) {
+            ("bet", ["minus"]) => match bet_minus(&mut game) {
                 Ok(val) => println!("Bet size: {}.", val),
                 Err(e) => println!("{}", e),
             },
-            "SPIN" => spin(&mut game),
-            val if val.starts_with("AUTOSPIN") => {
-                let split = val.split(" ");
-
-                let number_spins = split.last().unwrap().parse::<u32>().unwrap();
-
-                for _ in 0..number_spins {
-                    spin(&mut game);
-                    sleep(Duration::from_secs(1));
+            ("spin", []) => spin(&mut game),
+            ("autospin", [number]) => match number.parse::<u32>() {
+                Ok(number_spins) => {
+                    for _ in 0..number_spins {
+                        spin(&mut game);
+                        sleep(Duration::from_secs(1));
+                    }
+                }
+                Err(_) => println!("Invalid number!"),
+            },
+            ("diagnose", [number]) => match number.parse::<u32>() {
+                Ok(number_spins) => diagnose(number_spins),
+                Err(_) => println!("Invalid number!"),
+            },
+            ("deposit", [number]) => match number.parse::<u32>() {
+                Ok(amount) => match game.deposit(amount) {
+                    Ok(()) => println!("Your balance: {} credits.", game.credits()),
+                    Err(e) => println!("{}", e),
+                },
+                Err(_) => println!("Invalid number!"),
+            },
+            ("cashout", []) => match game.cash_out() {
+                Ok(amount) => println!("You cash out {} credits.", amount),
+                Err(e) => println!("{}", e),
+            },
+            ("history", []) => {
+                for record in game.history().last(10) {
+                    println!(
+                        "Bet {}, win {}, balance {}",
+                        record.bet, record.win, record.credits
+                    );
+                }
+                if let Some(record) = game.history().biggest_win() {
+                    println!("Biggest win: {} credits", record.win);
                 }
             }
-            "PAYOUTS" => {
-                println!("3 Jackpot = x1666");
-                println!("3 Seven = x300");
-                println!("3 TripleBar = x100");
-                println!("3 DoubleBar = x50");
-                println!("3 Bar = x25");
-                println!("3 of any Bar = x12");
-                println!("3 Cherry = x12");
-                println!("2 Cherry = x6");
-                println!("1 Cherry = x3");
-            }
-            "HELP" => print_help(),
+            ("payouts", []) => browse_paytable(&game, locale),
+            ("help", []) => print_help(locale),
             _ => println!("Invalid command!"),
         }
+
+        if let Err(e) = game.save_to_file(SAVE_FILE, SaveFormat::Json) {
+            println!("Failed to save the game: {}", e);
+        }
     }
 }
 
+// Offers to resume the game saved in `SAVE_FILE`, starts a new game otherwise
+fn start(locale: Locale) -> Game {
+    match Game::load_from_file(SAVE_FILE, SaveFormat::Json) {
+        Ok(game) => {
+            println!(
+                "{}",
+                locale
+                    .message("resume")
+                    .replace("{balance}", &game.credits().to_string())
+                    .replace("{bet}", &game.bet().to_string())
+            );
+
+            let mut answer = String::new();
+            std::io::stdin()
+                .read_line(&mut answer)
+                .expect("Failed to read command!");
+            if !answer.trim().to_lowercase().starts_with('n') {
+                return game;
+            }
+        }
+        Err(GameError::Io(e)) if e.kind() == ErrorKind::NotFound => {}
+        Err(e) => println!("Failed to load the saved game, starting a new one: {}", e),
+    }
+
+    Game::new(BALANCE, BET_SIZE, BET_MIN, BET_MAX, Paytable::default()).unwrap()
+}
+
 fn spin(game: &mut Game) {
-    let symbols = game.spin();
-    match symbols {
+    match game.spin() {
         Ok(val) => {
-            println!("{:?}", val);
-            println!("You win {} credits", game.win());
+            for row in 0..NUM_ROWS {
+                println!("{:?}", val.window.row(row));
+            }
+            for line in val.lines {
+                println!("Line {}: {}!", line.line + 1, line.combination);
+            }
+            if let Some(scatter) = val.scatter {
+                println!("Scatter: {}!", scatter.combination);
+            }
+            println!("You win {} credits", val.win);
+            if let GameMode::FreeSpins { remaining } = val.mode {
+                println!("Free spins left: {}", remaining);
+            }
+        }
+        Err(e) => println!("{}", e),
+    }
+}
+
+// Shows the rules of the paytable one by one, with an example board and its win at the current bet.
+// The input is line-based, an arrow key is read as its escape sequence once Enter is pressed.
+fn browse_paytable(game: &Game, locale: Locale) {
+    let rules = game.paytable().rules();
+    if rules.is_empty() {
+        println!("The paytable has no line rules.");
+        return;
+    }
+
+    let mut index = 0;
+    loop {
+        let rule = &rules[index];
+        println!(
+            "Rule {}/{}: {}, pays x{} = {} credits at bet {}",
+            index + 1,
+            rules.len(),
+            rule.name,
+            rule.pays,
+            rule.pays.saturating_mul(game.bet()),
+            game.bet()
+        );
+        let window = example(game, rule);
+        for row in 0..window.rows() {
+            println!("{:?}", window.row(row));
+        }
+        // Only the first payline is evaluated, an earlier rule may shadow this one.
+        let evaluation =
+            game.paytable()
+                .evaluate_window(&window, &game.paylines()[..1], game.bet());
+        match evaluation.lines.first() {
+            Some(line) => println!(
+                "The board wins {} credits: {}",
+                evaluation.win, line.combination
+            ),
+            None => println!("The board wins nothing"),
         }
-        Err(e) => println!("{}", e.to_owned()),
+        println!("{}", locale.message("paytable.navigate"));
+
+        let mut input = String::new();
+        if std::io::stdin().read_line(&mut input).unwrap_or(0) == 0 {
+            return;
+        }
+        match input.trim() {
+            "" | "n" | "\x1b[B" | "\x1b[C" => index = (index + 1) % rules.len(),
+            "p" | "\x1b[A" | "\x1b[D" => index = (index + rules.len() - 1) % rules.len(),
+            "q" => return,
+            _ => println!("Invalid command!"),
+        }
+    }
+}
+
+// A board with the combination of the `rule` on the first payline and filler symbols elsewhere
+fn example(game: &Game, rule: &Rule) -> Window {
+    let reels = game.reels().len();
+    let (symbols, count) = match &rule.combination {
+        Combination::AnyOf(symbols) => (symbols.clone(), reels),
+        Combination::Count { symbol, count } | Combination::FromLeft { symbol, count } => {
+            (vec![*symbol], *count)
+        }
+        Combination::CountOf { symbols, count } => (symbols.clone(), *count),
+    };
+    // The first symbol that is neither in the combination nor special
+    let filler = Symbol::ALL
+        .iter()
+        .copied()
+        .find(|x| !symbols.contains(x) && *x != Symbol::Wild && *x != Symbol::Scatter)
+        .unwrap_or(Symbol::Blank);
+
+    let mut board = vec![filler; reels * NUM_ROWS];
+    let payline = &game.paylines()[0];
+    for reel in 0..count.min(reels) {
+        board[payline.rows()[reel] * reels + reel] = symbols[reel % symbols.len()];
+    }
+
+    Window::new(reels, NUM_ROWS, board)
+}
+
+// Runs `number_spins` spins in a separate game and compares symbol frequencies with reel weights
+fn diagnose(number_spins: u32) {
+    let mut game = Game::new(
+        number_spins * BET_MIN,
+        BET_MIN,
+        BET_MIN,
+        BET_MAX,
+        Paytable::default(),
+    )
+    .unwrap();
+    let mut symbols = Vec::new();
+
+    for _ in 0..number_spins {
+        let window = game.spin().unwrap().window;
+        symbols.extend_from_slice(window.row(NUM_ROWS / 2));
+    }
+
+    // All reels use the same default strip
+    println!("{:<10} {:>9} {:>9}", "Symbol", "Expected", "Observed");
+    for frequency in compare(&game.reels()[0], &symbols) {
+        println!(
+            "{:<10} {:>8.2}% {:>8.2}%",
+            frequency.symbol.to_string(),
+            frequency.expected * 100.0,
+            frequency.observed * 100.0
+        );
     }
 }
 
@@ -107,11 +491,68 @@ fn bet_minus(game: &mut Game) -> Result<u32, String> {
     Ok(bet_size)
 }
 
-// Prints help text
-fn print_help() {
-    println!("To get a balance, put the `balance`.");
-    println!("To get a bet size, put the `bet`.");
-    println!("To increase or decrease the size of the bet, put `bet plus` or `bet minus`.");
-    println!("To activate auto-spin, put `autospin <NUMBER>` where NUMBER is the number of spins.");
-    println!("To show the payout table, put `payouts`.");
+// Prints help text in the `locale` generated from `COMMANDS`
+fn print_help(locale: Locale) {
+    for command in COMMANDS {
+        let mut usage = format!("`{}`", command.usage);
+        if !command.aliases.is_empty() {
+            usage += locale.message("or");
+            usage += &format!("`{}`", command.aliases.join("`, `"));
+        }
+
+        println!(
+            "{}",
+            locale
+                .message("help")
+                .replace("{description}", locale.message(command.description))
+                .replace("{usage}", &usage)
+        );
+    }
+}
+
+// Returns the name of the command matching the `word` exactly, by an alias or by a unique prefix
+fn resolve(word: &str) -> Result<&'static str, String> {
+    if let Some(command) = COMMANDS
+        .iter()
+        .find(|x| x.name == word || x.aliases.contains(&word))
+    {
+        return Ok(command.name);
+    }
+
+    let mut names: Vec<&'static str> = Vec::new();
+    for command in COMMANDS.iter().filter(|x| x.name.starts_with(word)) {
+        if !names.contains(&command.name) {
+            names.push(command.name);
+        }
+    }
+
+    match names.as_slice() {
+        [name] => Ok(name),
+        [] => Err("Invalid command!".to_owned()),
+        _ => Err(format!(
+            "Ambiguous command `{}`: {}?",
+            word,
+            names.join(", ")
+        )),
+    }
+}
+
+// Returns a description of every alias that clashes with another command or alias
+fn conflicts() -> Vec<String> {
+    let mut conflicts = Vec::new();
+
+    for command in COMMANDS {
+        for alias in command.aliases {
+            for other in COMMANDS.iter().filter(|x| x.name != command.name) {
+                if other.name == *alias || other.aliases.contains(alias) {
+                    conflicts.push(format!(
+                        "alias `{}` of `{}` conflicts with `{}`",
+                        alias, command.name, other.name
+                    ));
+                }
+            }
+        }
+    }
+
+    conflicts
 }
@@ -0,0 +1,153 @@
+use slot_machine::game::server::{ErrorBody, Sessions, API_VERSION, IDLE_TIMEOUT, MAX_SESSIONS};
+use std::env;
+use std::io::Read;
+use std::panic::{self, AssertUnwindSafe};
+use std::process;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use tiny_http::{Header, Request, Response, Server};
+
+const USAGE: &str = "Usage:
+    server [--address <HOST:PORT>] [--threads <NUMBER>] [--max-sessions <NUMBER>]
+        [--idle-timeout <SECONDS>]";
+
+// Command line options.
+struct Options {
+    address: String,
+    threads: usize,
+    max_sessions: usize,
+    idle_timeout: Duration,
+}
+
+// Requests with a larger body are rejected, a custom paytable fits easily.
+const MAX_BODY: u64 = 1 << 20;
+
+// How often the idle sessions are removed.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+
+fn main() {
+    let options = match parse_options(env::args().skip(1).collect()) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(2);
+        }
+    };
+
+    let server = match Server::http(&options.address) {
+        Ok(server) => Arc::new(server),
+        Err(e) => {
+            eprintln!("Failed to listen on {}: {}", options.address, e);
+            process::exit(1);
+        }
+    };
+    let sessions = Arc::new(Sessions::with_limits(
+        options.max_sessions,
+        options.idle_timeout,
+    ));
+    println!("Listening on http://{}", options.address);
+
+    let pruned = Arc::clone(&sessions);
+    thread::spawn(move || loop {
+        thread::sleep(PRUNE_INTERVAL);
+        pruned.prune();
+    });
+
+    let workers: Vec<_> = (0..options.threads)
+        .map(|_| {
+            let server = Arc::clone(&server);
+            let sessions = Arc::clone(&sessions);
+
+            thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    serve(&sessions, request);
+                }
+            })
+        })
+        .collect();
+
+    for worker in workers {
+        let _ = worker.join();
+    }
+}
+
+// Parses the address, the number of worker threads and the session limits.
+fn parse_options(args: Vec<String>) -> Result<Options, String> {
+    let mut options = Options {
+        address: "127.0.0.1:8080".to_owned(),
+        threads: thread::available_parallelism().map_or(1, |x| x.get()),
+        max_sessions: MAX_SESSIONS,
+        idle_timeout: IDLE_TIMEOUT,
+    };
+    let mut args = args.into_iter();
+
+    while let Some(option) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| format!("Missing value of `{}`!\n{}", option, USAGE))?;
+
+        match option.as_str() {
+            "--address" => options.address = value,
+            "--threads" => options.threads = parse_positive(&option, &value)?,
+            "--max-sessions" => options.max_sessions = parse_positive(&option, &value)?,
+            "--idle-timeout" => {
+                let seconds = parse_positive(&option, &value)?;
+                options.idle_timeout = Duration::from_secs(seconds as u64);
+            }
+            _ => return Err(format!("Unknown option `{}`!\n{}", option, USAGE)),
+        }
+    }
+
+    Ok(options)
+}
+
+// Parses the `value` of the `option` as a positive number.
+fn parse_positive(option: &str, value: &str) -> Result<usize, String> {
+    match value.parse() {
+        Ok(0) | Err(_) => Err(format!(
+            "`{}` must be a positive number, got `{}`!",
+            option, value
+        )),
+        Ok(number) => Ok(number),
+    }
+}
+
+// Answers the `request` from the `sessions`.
+fn serve(sessions: &Sessions, mut request: Request) {
+    let mut body = String::new();
+    let read = request.as_reader().take(MAX_BODY).read_to_string(&mut body);
+
+    // A panic is answered with 500 and keeps the worker serving the next requests.
+    let response = match read {
+        Ok(_) => panic::catch_unwind(AssertUnwindSafe(|| {
+            sessions.handle(request.method().as_str(), request.url(), &body)
+        }))
+        .unwrap_or_else(|_| error(500, "Internal server error".to_owned())),
+        Err(e) => error(400, format!("Failed to read the request body: {}", e)),
+    };
+
+    let content_type = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+        .expect("the header is valid");
+    let version = Header::from_bytes(&b"API-Version"[..], API_VERSION.to_string().as_bytes())
+        .expect("the header is valid");
+    let response = Response::from_string(response.body)
+        .with_status_code(response.status)
+        .with_header(content_type)
+        .with_header(version);
+
+    // The client may have disconnected, there is nobody to report the error to.
+    let _ = request.respond(response);
+}
+
+// Returns an error response of the HTTP layer.
+fn error(status: u16, message: String) -> slot_machine::game::server::Response {
+    slot_machine::game::server::Response {
+        status,
+        body: serde_json::to_string(&ErrorBody {
+            code: None,
+            message,
+        })
+        .expect("the error is serializable"),
+    }
+}
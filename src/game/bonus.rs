@@ -0,0 +1,159 @@
+use crate::game::symbol::Symbol;
+use crate::game::window::Window;
+use serde_derive::{Deserialize, Serialize};
+use std::fmt;
+
+/// Mode the [`Game`] is in.
+///
+/// [`Game`]: crate::game::Game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum GameMode {
+    /// Regular spins, the bet is deducted from the balance
+    #[default]
+    BaseGame,
+    /// Free spins, the bet is not deducted and wins are multiplied
+    FreeSpins {
+        /// Number of free spins left
+        remaining: u32,
+    },
+}
+
+impl fmt::Display for GameMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameMode::BaseGame => write!(f, "base game"),
+            GameMode::FreeSpins { remaining } => write!(f, "{} free spins", remaining),
+        }
+    }
+}
+
+/// Free spins bonus, awarded for [`Symbol::Scatter`] anywhere in the window.
+///
+/// # Examples
+///
+/// ```
+/// # use slot_machine::game::bonus::FreeSpins;
+/// let bonus = FreeSpins::new(3, 10, 2);
+///
+/// assert_eq!(bonus.count(), 10);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FreeSpins {
+    /// Minimum number of scatters awarding the bonus
+    scatters: usize,
+    /// Number of free spins awarded
+    count: u32,
+    /// Multiplier of the wins during free spins
+    multiplier: u32,
+}
+
+impl FreeSpins {
+    /// Creates new [`FreeSpins`]: `scatters` or more scatter symbols award `count` free spins,
+    /// wins during free spins are multiplied by `multiplier`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scatters`, `count` or `multiplier` is 0.
+    pub fn new(scatters: usize, count: u32, multiplier: u32) -> Self {
+        assert!(scatters > 0, "`scatters` must be positive!");
+        assert!(count > 0, "`count` must be positive!");
+        assert!(multiplier > 0, "`multiplier` must be positive!");
+
+        FreeSpins {
+            scatters,
+            count,
+            multiplier,
+        }
+    }
+
+    /// Returns the minimum number of scatters awarding the bonus
+    pub fn scatters(&self) -> usize {
+        self.scatters
+    }
+
+    /// Returns the number of free spins awarded
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Returns the multiplier of the wins during free spins
+    pub fn multiplier(&self) -> u32 {
+        self.multiplier
+    }
+
+    /// Returns `true` if the `window` awards the bonus
+    pub fn is_triggered(&self, window: &Window) -> bool {
+        let scatters = window
+            .symbols()
+            .iter()
+            .filter(|&&x| x == Symbol::Scatter)
+            .count();
+
+        scatters >= self.scatters
+    }
+
+    /// Returns the mode after a spin in `mode` that showed the `window`.
+    ///
+    /// A free spin uses up one of the remaining spins, landing the scatters again
+    /// awards [`FreeSpins::count`] more.
+    pub fn next_mode(&self, mode: GameMode, window: &Window) -> GameMode {
+        let remaining = match mode {
+            GameMode::BaseGame => 0,
+            GameMode::FreeSpins { remaining } => remaining - 1,
+        };
+        let remaining = if self.is_triggered(window) {
+            remaining + self.count
+        } else {
+            remaining
+        };
+
+        if remaining == 0 {
+            GameMode::BaseGame
+        } else {
+            GameMode::FreeSpins { remaining }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::symbol::Symbol::*;
+
+    fn window(scatters: usize) -> Window {
+        let mut symbols = vec![Blank; 9];
+        for x in symbols.iter_mut().take(scatters) {
+            *x = Scatter;
+        }
+
+        Window::new(3, 3, symbols)
+    }
+
+    #[test]
+    fn next_mode() {
+        let bonus = FreeSpins::new(3, 10, 2);
+
+        assert_eq!(
+            bonus.next_mode(GameMode::BaseGame, &window(2)),
+            GameMode::BaseGame
+        );
+        assert_eq!(
+            bonus.next_mode(GameMode::BaseGame, &window(3)),
+            GameMode::FreeSpins { remaining: 10 }
+        );
+        assert_eq!(
+            bonus.next_mode(GameMode::FreeSpins { remaining: 1 }, &window(0)),
+            GameMode::BaseGame
+        );
+    }
+
+    #[test]
+    fn next_mode_retrigger() {
+        let bonus = FreeSpins::new(3, 10, 2);
+
+        assert_eq!(
+            bonus.next_mode(GameMode::FreeSpins { remaining: 4 }, &window(4)),
+            GameMode::FreeSpins { remaining: 13 }
+        );
+    }
+}
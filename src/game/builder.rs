@@ -0,0 +1,381 @@
+use crate::game::bonus::FreeSpins;
+use crate::game::error::{ConfigError, GameError};
+use crate::game::history::History;
+use crate::game::invariant::InvariantMode;
+use crate::game::jackpot::Jackpot;
+use crate::game::ledger::Ledger;
+use crate::game::payline::Payline;
+use crate::game::paytable::Paytable;
+use crate::game::reel::ReelStrip;
+use crate::game::simulation;
+use crate::game::{Game, NUM_REELS, NUM_ROWS};
+use rand::rngs::StdRng;
+
+/// Defines what happens when the calculated return to player differs from the advertised one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RtpPolicy {
+    /// [`GameBuilder::build`] fails with [`ConfigError::Rtp`]
+    Reject,
+    /// The game is built, the mismatch is returned by [`GameBuilder::build_with_warnings`]
+    Warn,
+}
+
+/// Builder of the [`Game`].
+///
+/// Every setting is optional: by default the game has no credits, a fixed bet of 1 credit,
+/// [`Paytable::default`], [`ReelStrip::default`] on [`NUM_REELS`] reels, the middle [`Payline`]
+/// and a generator seeded from the operating system entropy.
+///
+/// # Examples
+///
+/// ```
+/// # use rand::rngs::StdRng;
+/// # use rand::SeedableRng;
+/// # use slot_machine::game::payline::Payline;
+/// # use slot_machine::game::builder::GameBuilder;
+/// let game = GameBuilder::new()
+///     .credits(1000)
+///     .bet(1, 1, 100)
+///     .paylines(vec![Payline::horizontal(3, 0), Payline::horizontal(3, 1)])
+///     .rng(StdRng::seed_from_u64(42))
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(game.total_bet(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct GameBuilder {
+    credits: u32,
+    bet: u32,
+    bet_min: u32,
+    bet_max: u32,
+    paytable: Paytable,
+    reels: Vec<ReelStrip>,
+    paylines: Option<Vec<Payline>>,
+    jackpot: Option<Jackpot>,
+    free_spins: Option<FreeSpins>,
+    advertised_rtp: Option<(f64, f64)>,
+    rtp_policy: RtpPolicy,
+    history_capacity: usize,
+    ledger_capacity: usize,
+    rng: Option<StdRng>,
+    invariant_mode: InvariantMode,
+}
+
+impl GameBuilder {
+    /// Creates new [`GameBuilder`] with the default settings.
+    pub fn new() -> Self {
+        GameBuilder {
+            credits: 0,
+            bet: 1,
+            bet_min: 1,
+            bet_max: 1,
+            paytable: Paytable::default(),
+            reels: vec![ReelStrip::default(); NUM_REELS],
+            paylines: None,
+            jackpot: None,
+            free_spins: None,
+            advertised_rtp: None,
+            rtp_policy: RtpPolicy::Reject,
+            history_capacity: History::DEFAULT_CAPACITY,
+            ledger_capacity: Ledger::DEFAULT_CAPACITY,
+            rng: None,
+            invariant_mode: InvariantMode::default(),
+        }
+    }
+
+    /// Sets the initial balance
+    pub fn credits(mut self, credits: u32) -> Self {
+        self.credits = credits;
+        self
+    }
+
+    /// Sets the bet size per line and the bet limits
+    pub fn bet(mut self, bet: u32, bet_min: u32, bet_max: u32) -> Self {
+        self.bet = bet;
+        self.bet_min = bet_min;
+        self.bet_max = bet_max;
+        self
+    }
+
+    /// Sets the paytable
+    pub fn paytable(mut self, paytable: Paytable) -> Self {
+        self.paytable = paytable;
+        self
+    }
+
+    /// Sets the reel strips, one per reel, any number of reels is allowed
+    pub fn reels(mut self, reels: Vec<ReelStrip>) -> Self {
+        self.reels = reels;
+        self
+    }
+
+    /// Sets the active paylines, by default the middle row across all reels
+    pub fn paylines(mut self, paylines: Vec<Payline>) -> Self {
+        self.paylines = Some(paylines);
+        self
+    }
+
+    /// Sets the progressive jackpot
+    pub fn jackpot(mut self, jackpot: Jackpot) -> Self {
+        self.jackpot = Some(jackpot);
+        self
+    }
+
+    /// Sets the free spins bonus
+    pub fn free_spins(mut self, free_spins: FreeSpins) -> Self {
+        self.free_spins = Some(free_spins);
+        self
+    }
+
+    /// Sets the number of spins kept in the history
+    pub fn history_capacity(mut self, capacity: usize) -> Self {
+        self.history_capacity = capacity;
+        self
+    }
+
+    /// Sets the number of transactions kept in the ledger
+    pub fn ledger_capacity(mut self, capacity: usize) -> Self {
+        self.ledger_capacity = capacity;
+        self
+    }
+
+    /// Sets the advertised return to player, e.g. `0.92` for 92%.
+    ///
+    /// [`GameBuilder::build`] calculates the exact return to player of the configuration and
+    /// compares it with `rtp`, the difference must not exceed `tolerance`.
+    /// This catches reel strips or paytables edited without updating the documentation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use slot_machine::game::builder::GameBuilder;
+    /// assert!(GameBuilder::new().advertised_rtp(0.92, 0.01).build().is_ok());
+    /// assert!(GameBuilder::new().advertised_rtp(0.95, 0.01).build().is_err());
+    /// ```
+    pub fn advertised_rtp(mut self, rtp: f64, tolerance: f64) -> Self {
+        self.advertised_rtp = Some((rtp, tolerance));
+        self
+    }
+
+    /// Sets what happens when the advertised return to player does not match,
+    /// [`RtpPolicy::Reject`] by default
+    pub fn rtp_policy(mut self, policy: RtpPolicy) -> Self {
+        self.rtp_policy = policy;
+        self
+    }
+
+    /// Sets the random number generator used to spin the reels
+    pub fn rng(mut self, rng: StdRng) -> Self {
+        self.rng = Some(rng);
+        self
+    }
+
+    /// Sets what happens when a spin breaks one of the game invariants
+    pub fn invariant_mode(mut self, mode: InvariantMode) -> Self {
+        self.invariant_mode = mode;
+        self
+    }
+
+    /// Validates the settings and creates the [`Game`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GameError::InvalidBet`] if bet_min > bet_max or bet < bet_min or bet > bet_max,
+    /// [`GameError::InvalidConfig`] with [`ConfigError::Reels`] if there are no reels,
+    /// with [`ConfigError::Paylines`] if there are no paylines or a payline does not fit into the window,
+    /// with [`ConfigError::Pays`] if a paytable rule pays too much and with [`ConfigError::Rtp`] if the return to player
+    /// does not match [`GameBuilder::advertised_rtp`] under [`RtpPolicy::Reject`].
+    pub fn build(self) -> Result<Game, GameError> {
+        self.build_with_warnings().map(|(game, _)| game)
+    }
+
+    /// Same as [`GameBuilder::build`], also returns the problems that did not stop the build,
+    /// e.g. [`ConfigError::Rtp`] under [`RtpPolicy::Warn`].
+    ///
+    /// # Errors
+    ///
+    /// See [`GameBuilder::build`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use slot_machine::game::builder::{GameBuilder, RtpPolicy};
+    /// let (_game, warnings) = GameBuilder::new()
+    ///     .advertised_rtp(0.97, 0.01)
+    ///     .rtp_policy(RtpPolicy::Warn)
+    ///     .build_with_warnings()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(warnings.len(), 1);
+    /// ```
+    pub fn build_with_warnings(self) -> Result<(Game, Vec<ConfigError>), GameError> {
+        let reels = self.reels.len();
+        if reels == 0 {
+            return Err(ConfigError::Reels(reels).into());
+        }
+
+        let paylines = self
+            .paylines
+            .unwrap_or_else(|| vec![Payline::horizontal(reels, NUM_ROWS / 2)]);
+        if paylines.is_empty() || !paylines.iter().all(|x| x.fits(reels, NUM_ROWS)) {
+            return Err(ConfigError::Paylines.into());
+        }
+
+        let mut game = Game::without_lookup(
+            self.credits,
+            self.bet,
+            self.bet_min,
+            self.bet_max,
+            self.paytable,
+        )?;
+
+        game.reels = self.reels;
+        game.paylines = paylines;
+        game.jackpot = self.jackpot;
+        game.free_spins = self.free_spins;
+        game.set_history_capacity(self.history_capacity);
+        game.set_ledger_capacity(self.ledger_capacity);
+        game.invariant_mode = self.invariant_mode;
+
+        let mut warnings = Vec::new();
+        if let Some((advertised, tolerance)) = self.advertised_rtp {
+            let actual = simulation::exact_rtp(&game).rtp;
+
+            if (actual - advertised).abs() > tolerance {
+                let error = ConfigError::Rtp { advertised, actual };
+
+                match self.rtp_policy {
+                    RtpPolicy::Reject => return Err(error.into()),
+                    RtpPolicy::Warn => warnings.push(error),
+                }
+            }
+        }
+
+        if let Some(rng) = self.rng {
+            game = game.with_rng(rng);
+        }
+
+        game.index();
+
+        Ok((game, warnings))
+    }
+}
+
+impl Default for GameBuilder {
+    fn default() -> Self {
+        GameBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::error::ErrorCategory;
+    use rand::SeedableRng;
+
+    #[test]
+    fn build_default() {
+        let game = GameBuilder::new().credits(1000).build().unwrap();
+
+        assert_eq!(game, Game::new(1000, 1, 1, 1, Paytable::default()).unwrap());
+    }
+
+    #[test]
+    fn build_seeded() {
+        let build = || {
+            GameBuilder::new()
+                .credits(1000)
+                .bet(2, 1, 10)
+                .rng(StdRng::seed_from_u64(1))
+                .build()
+                .unwrap()
+        };
+        let mut a = build();
+        let mut b = build();
+
+        assert_eq!(a.spin().unwrap(), b.spin().unwrap());
+    }
+
+    #[test]
+    fn build_invalid_bet() {
+        let error = GameBuilder::new().bet(20, 1, 10).build().unwrap_err();
+
+        assert!(matches!(error, GameError::InvalidBet { bet: 20, .. }));
+        assert_eq!(error.code(), 1001);
+    }
+
+    #[test]
+    fn build_shares_lookup() {
+        let game = GameBuilder::new().build().unwrap();
+        let clone = game.clone();
+
+        assert!(std::sync::Arc::ptr_eq(
+            game.lookup.as_ref().unwrap(),
+            clone.lookup.as_ref().unwrap()
+        ));
+        assert!(GameBuilder::new()
+            .reels(vec![ReelStrip::default(); 5])
+            .build()
+            .unwrap()
+            .lookup
+            .is_none());
+    }
+
+    #[test]
+    fn build_invalid_reels() {
+        let error = GameBuilder::new().reels(vec![]).build().unwrap_err();
+
+        assert!(matches!(
+            error,
+            GameError::InvalidConfig(ConfigError::Reels(0))
+        ));
+        assert_eq!(error.code(), 5001);
+        assert_eq!(error.category(), ErrorCategory::Config);
+    }
+
+    #[test]
+    fn build_five_reels() {
+        let game = GameBuilder::new()
+            .reels(vec![ReelStrip::default(); 5])
+            .build()
+            .unwrap();
+
+        assert_eq!(game.reels().len(), 5);
+        assert_eq!(game.paylines(), &[Payline::horizontal(5, 1)]);
+        assert!(GameBuilder::new()
+            .reels(vec![ReelStrip::default(); 5])
+            .paylines(vec![Payline::horizontal(NUM_REELS, 1)])
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn build_advertised_rtp() {
+        let error = GameBuilder::new()
+            .advertised_rtp(0.97, 0.01)
+            .build()
+            .unwrap_err();
+
+        assert_eq!(error.code(), 5003);
+        let (_, warnings) = GameBuilder::new()
+            .advertised_rtp(0.97, 0.01)
+            .rtp_policy(RtpPolicy::Warn)
+            .build_with_warnings()
+            .unwrap();
+        assert!(matches!(warnings[..], [ConfigError::Rtp { .. }]));
+        assert!(GameBuilder::new()
+            .advertised_rtp(0.92, 0.01)
+            .build_with_warnings()
+            .unwrap()
+            .1
+            .is_empty());
+    }
+
+    #[test]
+    fn build_invalid_paylines() {
+        let error = GameBuilder::new().paylines(vec![]).build().unwrap_err();
+
+        assert_eq!(error.code(), 5002);
+    }
+}
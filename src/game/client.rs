@@ -0,0 +1,269 @@
+use crate::game::server::{ErrorBody, NewBet, NewGame, Session, API_VERSION};
+use crate::game::spin::SpinResult;
+use serde::de::DeserializeOwned;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+
+/// Default timeout of reading and writing a request of a [`Client`].
+pub const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Error of a [`Client`] request.
+#[derive(Debug)]
+pub enum ClientError {
+    /// The server could not be reached or the connection failed
+    Io(io::Error),
+    /// The response is not valid HTTP or its body is not the expected Json
+    InvalidResponse(String),
+    /// The server answered with an error
+    Server {
+        /// HTTP status code
+        status: u16,
+        /// Body of the error, see [`ErrorBody::code`] for the error of the game
+        error: ErrorBody,
+    },
+}
+
+impl Error for ClientError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ClientError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Io(e) => write!(f, "Request failed: {}", e),
+            ClientError::InvalidResponse(e) => write!(f, "Invalid response: {}", e),
+            ClientError::Server { status, error } => {
+                write!(f, "Server error {}: {}", status, error.message)
+            }
+        }
+    }
+}
+
+impl From<io::Error> for ClientError {
+    fn from(e: io::Error) -> Self {
+        ClientError::Io(e)
+    }
+}
+
+/// Typed blocking client of the REST API served by [`Sessions`].
+///
+/// Every method sends one request over a new connection to the current [`API_VERSION`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # use slot_machine::game::client::Client;
+/// # use slot_machine::game::server::NewGame;
+/// let client = Client::new("127.0.0.1:8080");
+/// let session = client.create(&NewGame {
+///     credits: 100,
+///     ..NewGame::default()
+/// })?;
+///
+/// let result = client.spin(session.id)?;
+/// println!("You win {} credits", result.win);
+/// # Ok::<(), slot_machine::game::client::ClientError>(())
+/// ```
+///
+/// [`Sessions`]: crate::game::server::Sessions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Client {
+    address: String,
+    timeout: Duration,
+}
+
+impl Client {
+    /// Creates a client of the server listening on the `address`, e.g. `127.0.0.1:8080`.
+    pub fn new<S: Into<String>>(address: S) -> Self {
+        Client {
+            address: address.into(),
+            timeout: TIMEOUT,
+        }
+    }
+
+    /// Timeout setter, applies to reading and writing every request.
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    /// Starts a new game, `POST /games`.
+    pub fn create(&self, request: &NewGame) -> Result<Session, ClientError> {
+        let body = serde_json::to_string(request).expect("the request is serializable");
+
+        self.request("POST", "/games", &body)
+    }
+
+    /// Returns the meters of the game, `GET /games/{id}`.
+    pub fn session(&self, id: u64) -> Result<Session, ClientError> {
+        self.request("GET", &format!("/games/{}", id), "")
+    }
+
+    /// Spins the reels of the game, `POST /games/{id}/spin`.
+    pub fn spin(&self, id: u64) -> Result<SpinResult, ClientError> {
+        self.request("POST", &format!("/games/{}/spin", id), "")
+    }
+
+    /// Changes the bet size of the game, `PUT /games/{id}/bet`.
+    pub fn set_bet(&self, id: u64, bet: u32) -> Result<Session, ClientError> {
+        let body = serde_json::to_string(&NewBet { bet }).expect("the request is serializable");
+
+        self.request("PUT", &format!("/games/{}/bet", id), &body)
+    }
+
+    /// Ends the game, `DELETE /games/{id}`.
+    pub fn delete(&self, id: u64) -> Result<(), ClientError> {
+        self.send("DELETE", &format!("/games/{}", id), "")
+            .map(|_| ())
+    }
+
+    // Sends the request and parses the body of a successful response.
+    fn request<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        path: &str,
+        body: &str,
+    ) -> Result<T, ClientError> {
+        let response = self.send(method, path, body)?;
+
+        serde_json::from_str(&response).map_err(|e| ClientError::InvalidResponse(e.to_string()))
+    }
+
+    // Sends the request and returns the body of a successful response.
+    fn send(&self, method: &str, path: &str, body: &str) -> Result<String, ClientError> {
+        let mut stream = TcpStream::connect(&self.address)?;
+        stream.set_read_timeout(Some(self.timeout))?;
+        stream.set_write_timeout(Some(self.timeout))?;
+
+        // The connection is closed after the response, so the body ends with the stream.
+        write!(
+            stream,
+            "{} /v{}{} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\n\
+             Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+            method,
+            API_VERSION,
+            path,
+            self.address,
+            body.len(),
+            body
+        )?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response)?;
+
+        let (status, body) = parse_response(&response)?;
+        if (200..300).contains(&status) {
+            Ok(body.to_owned())
+        } else {
+            let error = serde_json::from_str(body).unwrap_or_else(|_| ErrorBody {
+                code: None,
+                message: body.to_owned(),
+            });
+
+            Err(ClientError::Server { status, error })
+        }
+    }
+}
+
+// Splits an HTTP response into the status code and the body.
+fn parse_response(response: &str) -> Result<(u16, &str), ClientError> {
+    let invalid = || ClientError::InvalidResponse("malformed HTTP response".to_owned());
+    let (head, body) = response.split_once("\r\n\r\n").ok_or_else(invalid)?;
+    let status = head
+        .split_whitespace()
+        .nth(1)
+        .and_then(|x| x.parse().ok())
+        .ok_or_else(invalid)?;
+
+    Ok((status, body))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::server::Sessions;
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+    use std::thread;
+
+    // Serves `requests` requests from a new store of sessions, returns its address.
+    fn serve(requests: usize) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+
+        thread::spawn(move || {
+            let sessions = Sessions::new();
+            for stream in listener.incoming().take(requests) {
+                let mut stream = BufReader::new(stream.unwrap());
+                let mut line = String::new();
+                stream.read_line(&mut line).unwrap();
+                let request: Vec<String> = line.split(' ').map(str::to_owned).collect();
+
+                let mut length = 0;
+                loop {
+                    line.clear();
+                    stream.read_line(&mut line).unwrap();
+                    match line.trim_end().split_once(": ") {
+                        Some(("Content-Length", value)) => length = value.parse().unwrap(),
+                        Some(_) => {}
+                        None => break,
+                    }
+                }
+                let mut body = vec![0; length];
+                stream.read_exact(&mut body).unwrap();
+
+                let response =
+                    sessions.handle(&request[0], &request[1], &String::from_utf8(body).unwrap());
+                write!(
+                    stream.get_mut(),
+                    "HTTP/1.1 {} OK\r\n\r\n{}",
+                    response.status,
+                    response.body
+                )
+                .unwrap();
+            }
+        });
+
+        address
+    }
+
+    #[test]
+    fn round_trip() {
+        let client = Client::new(serve(6));
+
+        let session = client
+            .create(&NewGame {
+                credits: 100,
+                bet_max: 10,
+                ..NewGame::default()
+            })
+            .unwrap();
+        assert_eq!(session.game.credits, 100);
+
+        let result = client.spin(session.id).unwrap();
+        assert_eq!(result.bet, 1);
+
+        let session = client.set_bet(session.id, 5).unwrap();
+        assert_eq!(session.game.bet, 5);
+
+        match client.set_bet(session.id, 50) {
+            Err(ClientError::Server { status, error }) => {
+                assert_eq!(status, 400);
+                assert_eq!(error.code, Some(1001));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        client.delete(session.id).unwrap();
+        assert!(matches!(
+            client.session(session.id),
+            Err(ClientError::Server { status: 404, .. })
+        ));
+    }
+}
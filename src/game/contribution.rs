@@ -0,0 +1,146 @@
+use crate::game::spin::{LineWin, SpinResult};
+use crate::game::symbol::Symbol;
+use crate::game::window::Window;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Wins attributed to the paytable rules and to the paying symbols.
+///
+/// A [`Game`] keeps the totals of its lifetime, see [`Game::contributions`], and so does
+/// a [`SimulationReport`]. Divide a total by the total bet to compare it with the
+/// theoretical [`ExactRtp::contributions`].
+///
+/// # Examples
+///
+/// ```
+/// # use slot_machine::game::paytable::Paytable;
+/// # use slot_machine::game::Game;
+/// let mut game = Game::new(1000, 1, 1, 100, Paytable::default()).unwrap();
+/// for _ in 0..100 {
+///     game.spin().unwrap();
+/// }
+///
+/// let contributions = game.contributions();
+/// assert_eq!(contributions.total(), game.history().total_won());
+/// ```
+///
+/// [`Game`]: crate::game::Game
+/// [`Game::contributions`]: crate::game::Game::contributions
+/// [`SimulationReport`]: crate::game::simulation::SimulationReport
+/// [`ExactRtp::contributions`]: crate::game::simulation::ExactRtp::contributions
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Contributions {
+    /// Sum of the wins per name of the line or scatter rule, jackpot lines excluded
+    pub by_rule: BTreeMap<String, u64>,
+    /// Sum of the wins per paying symbol, jackpot lines excluded
+    pub by_symbol: BTreeMap<Symbol, u64>,
+    /// Sum of the progressive jackpots won
+    pub jackpot: u64,
+}
+
+impl Contributions {
+    /// Returns the sum of all attributed wins
+    pub fn total(&self) -> u64 {
+        self.by_rule.values().sum::<u64>() + self.jackpot
+    }
+
+    /// Attributes the wins of the spin.
+    pub fn record(&mut self, result: &SpinResult) {
+        for line in &result.lines {
+            if line.jackpot {
+                self.jackpot += u64::from(line.win);
+            } else {
+                add(&mut self.by_rule, &line.combination, line.win);
+                *self
+                    .by_symbol
+                    .entry(paying_symbol(line, &result.window))
+                    .or_insert(0) += u64::from(line.win);
+            }
+        }
+
+        if let Some(scatter) = &result.scatter {
+            add(&mut self.by_rule, &scatter.combination, scatter.win);
+            *self.by_symbol.entry(Symbol::Scatter).or_insert(0) += u64::from(scatter.win);
+        }
+    }
+
+    /// Adds the totals of the `other` contributions to `self`.
+    pub fn merge(&mut self, other: &Contributions) {
+        for (rule, win) in &other.by_rule {
+            *self.by_rule.entry(rule.clone()).or_insert(0) += win;
+        }
+        for (symbol, win) in &other.by_symbol {
+            *self.by_symbol.entry(*symbol).or_insert(0) += win;
+        }
+        self.jackpot += other.jackpot;
+    }
+}
+
+// Adds the `win` to the total of the `rule`, the name is copied only for a new rule.
+fn add(totals: &mut BTreeMap<String, u64>, rule: &str, win: u32) {
+    match totals.get_mut(rule) {
+        Some(total) => *total += u64::from(win),
+        None => {
+            totals.insert(rule.to_owned(), u64::from(win));
+        }
+    }
+}
+
+// The first symbol forming the combination that is not a wild,
+// a line of wilds only is paid for the wild.
+fn paying_symbol(line: &LineWin, window: &Window) -> Symbol {
+    line.positions
+        .iter()
+        .map(|x| window.at(x.reel, x.row))
+        .find(|&x| x != Symbol::Wild)
+        .unwrap_or(Symbol::Wild)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::bonus::GameMode;
+    use crate::game::spin::ScatterWin;
+    use crate::game::symbol::Symbol::*;
+    use crate::game::window::Position;
+
+    #[test]
+    fn record_and_merge() {
+        let position = |reel| Position { reel, row: 0 };
+        let line = |combination: &str, jackpot| LineWin {
+            line: 0,
+            symbols: vec![Wild, Seven, Seven],
+            positions: vec![position(0), position(1), position(2)],
+            combination: combination.to_owned(),
+            win: 10,
+            jackpot,
+        };
+        let result = SpinResult {
+            window: Window::new(3, 1, vec![Wild, Seven, Seven]),
+            bet: 1,
+            win: 25,
+            lines: vec![line("3 Seven", false), line("3 Jackpot", true)],
+            scatter: Some(ScatterWin {
+                count: 3,
+                positions: vec![],
+                combination: "3 Scatter".to_owned(),
+                win: 5,
+            }),
+            multiplier: 1,
+            mode: GameMode::BaseGame,
+            credits: 0,
+        };
+
+        let mut contributions = Contributions::default();
+        contributions.record(&result);
+        contributions.merge(&contributions.clone());
+
+        assert_eq!(contributions.by_rule["3 Seven"], 20);
+        assert_eq!(contributions.by_rule["3 Scatter"], 10);
+        assert!(!contributions.by_rule.contains_key("3 Jackpot"));
+        assert_eq!(contributions.by_symbol[&Seven], 20);
+        assert_eq!(contributions.by_symbol[&Scatter], 10);
+        assert_eq!(contributions.jackpot, 20);
+        assert_eq!(contributions.total(), 50);
+    }
+}
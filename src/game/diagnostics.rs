@@ -0,0 +1,299 @@
+use crate::game::builder::GameBuilder;
+use crate::game::error::GameError;
+use crate::game::lookup::LookupTable;
+use crate::game::payline::Payline;
+use crate::game::payout::payout;
+use crate::game::paytable::Paytable;
+use crate::game::reel::ReelStrip;
+use crate::game::simulation::top_up_and_spin;
+use crate::game::spin::SpinResult;
+use crate::game::symbol::Symbol;
+use crate::game::window::Window;
+use crate::game::{NUM_REELS, NUM_ROWS};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::ops::Range;
+
+/// Expected and observed frequency of a [`Symbol`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolFrequency {
+    /// The symbol
+    pub symbol: Symbol,
+    /// Frequency derived from the reel weights, in range `0.0..=1.0`
+    pub expected: f64,
+    /// Frequency of the symbol in the observed sample, in range `0.0..=1.0`
+    pub observed: f64,
+}
+
+impl SymbolFrequency {
+    /// Returns the difference between the observed and expected frequency.
+    pub fn deviation(&self) -> f64 {
+        self.observed - self.expected
+    }
+}
+
+/// Compares how often each symbol appears in `symbols` against the weights of the `reel`.
+///
+/// Used to verify that the weighted mapping is applied to the symbols shown on the reel.
+/// Returns one entry per symbol in the order of [`Symbol::ALL`].
+///
+/// # Examples
+///
+/// ```
+/// # use slot_machine::game::diagnostics::compare;
+/// # use slot_machine::game::reel::ReelStrip;
+/// # use slot_machine::game::symbol::Symbol;
+/// let reel = ReelStrip::default();
+/// let mut rng = rand::thread_rng();
+/// let symbols: Vec<Symbol> = (0..10_000)
+///     .map(|_| reel.symbol(reel.random_index(&mut rng)))
+///     .collect();
+///
+/// for frequency in compare(&reel, &symbols) {
+///     assert!(frequency.deviation().abs() < 0.05);
+/// }
+/// ```
+pub fn compare(reel: &ReelStrip, symbols: &[Symbol]) -> Vec<SymbolFrequency> {
+    let total_weight = f64::from(reel.total_weight());
+
+    Symbol::ALL
+        .iter()
+        .map(|symbol| {
+            let count = symbols.iter().filter(|x| x == &symbol).count();
+            let observed = if symbols.is_empty() {
+                0.0
+            } else {
+                count as f64 / symbols.len() as f64
+            };
+
+            SymbolFrequency {
+                symbol: *symbol,
+                expected: f64::from(reel.weight_of(*symbol)) / total_weight,
+                observed,
+            }
+        })
+        .collect()
+}
+
+/// A payline on which the legacy [`payout`] and the generic evaluators disagree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PayoutMismatch {
+    /// Seed of the window, [`differential`] with this seed and one window reproduces it
+    pub seed: u64,
+    /// The window
+    pub window: Window,
+    /// Index of the payline
+    pub line: usize,
+    /// Payout multiplier of the legacy evaluator
+    pub legacy: u32,
+    /// Payout multiplier of the paytable
+    pub paytable: u32,
+    /// Payout multiplier of the precomputed lookup table, `None` if the game has none
+    pub lookup: Option<u32>,
+}
+
+/// Evaluates `windows` random windows of the `reels` on each of the `paylines` with the legacy
+/// [`payout`], the `paytable` and the lookup table built from both, returns every disagreement.
+///
+/// The window number `i` is drawn by a generator seeded with `seed + i`, so every mismatch can
+/// be replayed alone. Used to check that a paytable reproduces the legacy machine before
+/// switching to it.
+///
+/// # Panics
+///
+/// Panics if there are not [`NUM_REELS`] reels, the legacy evaluator knows only 3 reels,
+/// or a payline does not fit into the window.
+///
+/// # Examples
+///
+/// ```
+/// # use slot_machine::game::diagnostics::differential;
+/// # use slot_machine::game::payline::Payline;
+/// # use slot_machine::game::paytable::Paytable;
+/// # use slot_machine::game::reel::ReelStrip;
+/// let reels = vec![ReelStrip::default(); 3];
+/// let paylines = vec![Payline::horizontal(3, 0), Payline::horizontal(3, 1), Payline::horizontal(3, 2)];
+///
+/// assert!(differential(&Paytable::default(), &reels, &paylines, 42, 1000).is_empty());
+/// ```
+pub fn differential(
+    paytable: &Paytable,
+    reels: &[ReelStrip],
+    paylines: &[Payline],
+    seed: u64,
+    windows: u64,
+) -> Vec<PayoutMismatch> {
+    assert_eq!(
+        reels.len(),
+        NUM_REELS,
+        "The legacy evaluator needs {} reels, got {}!",
+        NUM_REELS,
+        reels.len()
+    );
+
+    let lookup = LookupTable::new(paytable, reels);
+    let mut mismatches = Vec::new();
+
+    for seed in (0..windows).map(|x| seed.wrapping_add(x)) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let stops: Vec<usize> = reels.iter().map(|x| x.random_index(&mut rng)).collect();
+        let window = Window::from_columns(
+            reels
+                .iter()
+                .zip(&stops)
+                .map(|(reel, &stop)| reel.window(stop, NUM_ROWS))
+                .collect(),
+        );
+        let lookup_lines = lookup
+            .as_ref()
+            .map(|x| x.evaluate_lines(paytable, reels, &stops, paylines, 1));
+
+        for (line, payline) in paylines.iter().enumerate() {
+            let symbols = payline.symbols(&window);
+            let legacy = payout(&symbols);
+            let generic = paytable.payout(&symbols);
+            let lookup = lookup_lines
+                .as_ref()
+                .map(|lines| lines.iter().find(|x| x.line == line).map_or(0, |x| x.win));
+
+            if legacy != generic || lookup.is_some_and(|x| x != legacy) {
+                mismatches.push(PayoutMismatch {
+                    seed,
+                    window: window.clone(),
+                    line,
+                    legacy,
+                    paytable: generic,
+                    lookup,
+                });
+            }
+        }
+    }
+
+    mismatches
+}
+
+/// Searches the `seeds` for a spin matching the `predicate`, e.g. a jackpot win for a
+/// screenshot, returns the first matching seed and its spin.
+///
+/// Every seed plays the first spin of the game configured by `config` with the generator
+/// seeded by it, so the board is reproduced by `config.rng(StdRng::seed_from_u64(seed))`.
+/// Like in [`simulate`] the balance of the `config` does not matter.
+///
+/// # Errors
+///
+/// Returns [`GameError`] if the `config` is invalid.
+///
+/// # Examples
+///
+/// ```
+/// # use slot_machine::game::builder::GameBuilder;
+/// # use slot_machine::game::diagnostics::find_seed;
+/// let config = GameBuilder::new();
+/// let found = find_seed(&config, 0..100_000, |x| x.win >= 100).unwrap();
+///
+/// let (_seed, result) = found.unwrap();
+/// assert!(result.win >= 100);
+/// ```
+///
+/// [`simulate`]: crate::game::simulation::simulate
+pub fn find_seed<F>(
+    config: &GameBuilder,
+    seeds: Range<u64>,
+    mut predicate: F,
+) -> Result<Option<(u64, SpinResult)>, GameError>
+where
+    F: FnMut(&SpinResult) -> bool,
+{
+    let game = config.clone().build()?;
+
+    for seed in seeds {
+        let mut game = game.clone().with_rng(StdRng::seed_from_u64(seed));
+        let result = top_up_and_spin(&mut game)?;
+
+        if predicate(&result) {
+            return Ok(Some((seed, result)));
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::symbol::Symbol::*;
+
+    #[test]
+    fn compare_exact_sample() {
+        let symbols: Vec<Symbol> = Symbol::RANGE
+            .map(|x| Symbol::from_number(x).unwrap())
+            .collect();
+
+        assert!(compare(&ReelStrip::default(), &symbols)
+            .iter()
+            .all(|x| x.deviation() == 0.0));
+    }
+
+    #[test]
+    fn compare_skewed_sample() {
+        let frequencies = compare(&ReelStrip::default(), &[Jackpot, Jackpot]);
+        let jackpot = frequencies.iter().find(|x| x.symbol == Jackpot).unwrap();
+
+        assert_eq!(jackpot.observed, 1.0);
+        assert_eq!(jackpot.expected, 2.0 / 128.0);
+    }
+
+    #[test]
+    fn differential_reports_changed_rule() {
+        let reels = vec![ReelStrip::default(); NUM_REELS];
+        let paylines = vec![Payline::default()];
+        let mut paytable = Paytable::default();
+        let mut rules = paytable.rules().to_vec();
+        rules.retain(|x| x.name != "1 Cherry");
+        paytable = Paytable::new(rules);
+
+        let mismatches = differential(&paytable, &reels, &paylines, 7, 2000);
+
+        assert!(!mismatches.is_empty());
+        for mismatch in &mismatches {
+            assert_eq!(mismatch.legacy, 3);
+            assert_eq!(mismatch.paytable, 0);
+            assert_eq!(mismatch.lookup, Some(0));
+            assert_eq!(
+                differential(&paytable, &reels, &paylines, mismatch.seed, 1),
+                vec![mismatch.clone()]
+            );
+        }
+        assert!(differential(&Paytable::default(), &reels, &paylines, 7, 2000).is_empty());
+    }
+
+    #[test]
+    fn find_seed_reproducible() {
+        let config = GameBuilder::new().credits(100);
+        let three_sevens = |x: &SpinResult| x.lines.iter().any(|x| x.combination == "3 Seven");
+
+        let (seed, result) = find_seed(&config, 0..100_000, three_sevens)
+            .unwrap()
+            .unwrap();
+        let replayed = config
+            .rng(StdRng::seed_from_u64(seed))
+            .build()
+            .unwrap()
+            .spin()
+            .unwrap();
+
+        assert_eq!(replayed.window, result.window);
+        assert_eq!(replayed.win, 300);
+        assert_eq!(
+            find_seed(&GameBuilder::new(), 0..0, |_| true).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn compare_empty_sample() {
+        assert!(compare(&ReelStrip::default(), &[])
+            .iter()
+            .all(|x| x.observed == 0.0));
+    }
+}
@@ -0,0 +1,360 @@
+use crate::game::paytable::MAX_PAYS;
+use crate::game::NUM_ROWS;
+use serde_derive::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::time::Duration;
+
+/// Category of an error, shared by all errors of the crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ErrorCategory {
+    /// Invalid input, e.g. bet size out of limits
+    Validation,
+    /// Not enough money to perform the operation
+    Funds,
+    /// The operation is not allowed in the current state of the game
+    State,
+    /// Failed to read or write persisted data
+    Storage,
+    /// Invalid game configuration
+    Config,
+}
+
+/// Error returned by every fallible operation of the crate.
+///
+/// # Examples
+///
+/// ```
+/// # use slot_machine::game::error::GameError;
+/// # use slot_machine::game::paytable::Paytable;
+/// # use slot_machine::game::Game;
+/// let mut game = Game::new(0, 1, 1, 100, Paytable::default()).unwrap();
+///
+/// match game.spin() {
+///     Err(GameError::LowBalance) => println!("Please insert credits"),
+///     Err(e) => println!("{}", e),
+///     Ok(result) => println!("You win {} credits", result.win),
+/// }
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum GameError {
+    /// There are not enough credits on the balance
+    LowBalance,
+    /// The bet size is out of the bet limits
+    InvalidBet {
+        /// Requested bet size
+        bet: u32,
+        /// Minimum bet size
+        bet_min: u32,
+        /// Maximum bet size
+        bet_max: u32,
+    },
+    /// The game configuration is invalid
+    InvalidConfig(ConfigError),
+    /// The random number generator failed
+    Rng(rand::Error),
+    /// Failed to serialize or deserialize data
+    Serialization(Box<dyn Error + Send + Sync>),
+    /// Failed to read or write a file
+    Io(io::Error),
+    /// The [`Wallet`] backend failed
+    ///
+    /// [`Wallet`]: crate::game::wallet::Wallet
+    Wallet {
+        /// Error of the backend
+        source: Box<dyn Error + Send + Sync>,
+        /// How long to wait before retrying, `None` if the failure is permanent,
+        /// e.g. `Some` for a timeout of a remote service
+        retry_after: Option<Duration>,
+    },
+    /// The balance changed outside the ledger in strict money mode
+    UnaccountedBalance {
+        /// Balance of the wallet
+        balance: u32,
+        /// Balance after the last transaction of the ledger
+        ledger: u32,
+    },
+}
+
+impl GameError {
+    /// Returns the stable numeric code of the error.
+    ///
+    /// Codes never change between versions, so they can be used in API responses,
+    /// logs and localized messages.
+    pub fn code(&self) -> u32 {
+        match self {
+            GameError::InvalidBet { .. } => 1001,
+            GameError::LowBalance => 2001,
+            GameError::Rng(_) => 3001,
+            GameError::UnaccountedBalance { .. } => 3002,
+            GameError::Serialization(_) => 4001,
+            GameError::Io(_) => 4002,
+            GameError::Wallet { .. } => 4003,
+            GameError::InvalidConfig(e) => e.code(),
+        }
+    }
+
+    /// Returns the category of the error.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            GameError::InvalidBet { .. } => ErrorCategory::Validation,
+            GameError::LowBalance => ErrorCategory::Funds,
+            GameError::Rng(_) | GameError::UnaccountedBalance { .. } => ErrorCategory::State,
+            GameError::Serialization(_) | GameError::Io(_) | GameError::Wallet { .. } => {
+                ErrorCategory::Storage
+            }
+            GameError::InvalidConfig(_) => ErrorCategory::Config,
+        }
+    }
+
+    /// Returns `true` if repeating the same operation may succeed.
+    ///
+    /// A failure of the operating system entropy source is transient, so is an I/O error that
+    /// timed out, was interrupted or would block. A [`GameError::Wallet`] is transient if its
+    /// backend gave a retry hint. The bet limits, the balance and the configuration do not
+    /// change by themselves.
+    pub fn is_retryable(&self) -> bool {
+        self.retry_after().is_some()
+    }
+
+    /// Returns how long to wait before retrying, `None` if the operation should not be retried.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            GameError::Rng(_) => Some(Duration::from_millis(100)),
+            GameError::Io(e) => match e.kind() {
+                io::ErrorKind::TimedOut
+                | io::ErrorKind::Interrupted
+                | io::ErrorKind::WouldBlock => Some(Duration::from_millis(100)),
+                _ => None,
+            },
+            GameError::Wallet { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+}
+
+impl Error for GameError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GameError::InvalidConfig(e) => Some(e),
+            GameError::Rng(e) => Some(e),
+            GameError::Serialization(e) => Some(e.as_ref()),
+            GameError::Io(e) => Some(e),
+            GameError::Wallet { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameError::LowBalance => write!(f, "Insufficient credits on the balance!"),
+            GameError::InvalidBet {
+                bet,
+                bet_min,
+                bet_max,
+            } => {
+                if bet_min > bet_max {
+                    write!(f, "bet_min > bet_max")
+                } else if bet < bet_min {
+                    write!(f, "bet < bet_min")
+                } else {
+                    write!(f, "bet > bet_max")
+                }
+            }
+            GameError::InvalidConfig(e) => write!(f, "Invalid configuration: {}", e),
+            GameError::Rng(e) => write!(f, "Random number generator failed: {}", e),
+            GameError::Serialization(e) => write!(f, "Serialization failed: {}", e),
+            GameError::Io(e) => write!(f, "I/O error: {}", e),
+            GameError::Wallet { source, .. } => write!(f, "Wallet error: {}", source),
+            GameError::UnaccountedBalance { balance, ledger } => write!(
+                f,
+                "The balance {} differs from the ledger balance {}",
+                balance, ledger
+            ),
+        }
+    }
+}
+
+impl From<ConfigError> for GameError {
+    fn from(e: ConfigError) -> Self {
+        GameError::InvalidConfig(e)
+    }
+}
+
+impl From<rand::Error> for GameError {
+    fn from(e: rand::Error) -> Self {
+        GameError::Rng(e)
+    }
+}
+
+impl From<serde_json::Error> for GameError {
+    fn from(e: serde_json::Error) -> Self {
+        GameError::Serialization(Box::new(e))
+    }
+}
+
+impl From<bincode::Error> for GameError {
+    fn from(e: bincode::Error) -> Self {
+        GameError::Serialization(e)
+    }
+}
+
+impl From<io::Error> for GameError {
+    fn from(e: io::Error) -> Self {
+        GameError::Io(e)
+    }
+}
+
+impl From<toml::de::Error> for GameError {
+    fn from(e: toml::de::Error) -> Self {
+        GameError::Serialization(Box::new(e))
+    }
+}
+
+/// The reason why a game configuration is invalid, see [`GameError::InvalidConfig`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum ConfigError {
+    /// There are no reel strips, holds the number of strips
+    Reels(usize),
+    /// There are no paylines or a payline does not fit into the reels x [`NUM_ROWS`]
+    Paylines,
+    /// The calculated return to player differs from the advertised one
+    Rtp {
+        /// Advertised return to player
+        advertised: f64,
+        /// Return to player calculated from the reels and the paytable
+        actual: f64,
+    },
+    /// A symbol set uses an id or a built-in symbol twice, holds the id of the duplicate
+    Symbols(String),
+    /// A paytable rule pays more than [`MAX_PAYS`], holds the name of the rule
+    Pays(String),
+    /// A reel strip has no stops, its total weight is 0 or does not fit into `u32`
+    Weights,
+    /// The free spins bonus needs no scatters, awards no spins or multiplies wins by 0
+    FreeSpins,
+    /// A spin of the history does not fit into the reels x [`NUM_ROWS`] window
+    History,
+}
+
+impl ConfigError {
+    /// Returns the stable numeric code of the error.
+    pub fn code(&self) -> u32 {
+        match self {
+            ConfigError::Reels(_) => 5001,
+            ConfigError::Paylines => 5002,
+            ConfigError::Rtp { .. } => 5003,
+            ConfigError::Symbols(_) => 5004,
+            ConfigError::Pays(_) => 5005,
+            ConfigError::Weights => 5006,
+            ConfigError::FreeSpins => 5007,
+            ConfigError::History => 5008,
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Reels(reels) => write!(f, "Expected at least one reel, got {}", reels),
+            ConfigError::Paylines => write!(
+                f,
+                "Paylines must not be empty and must cross every reel within {} rows",
+                NUM_ROWS
+            ),
+            ConfigError::Rtp { advertised, actual } => write!(
+                f,
+                "Advertised RTP {:.4} differs from the calculated {:.4}",
+                advertised, actual
+            ),
+            ConfigError::Symbols(id) => write!(f, "Symbol `{}` is defined twice", id),
+            ConfigError::Pays(rule) => write!(
+                f,
+                "Paytable rule `{}` pays more than {} times the bet",
+                rule, MAX_PAYS
+            ),
+            ConfigError::Weights => write!(
+                f,
+                "Every reel strip must have a positive total weight that fits into u32"
+            ),
+            ConfigError::FreeSpins => write!(
+                f,
+                "Free spins need positive numbers of scatters, spins and the multiplier"
+            ),
+            ConfigError::History => write!(
+                f,
+                "Spins of the history must fit into the reels and {} rows",
+                NUM_ROWS
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn codes_and_categories() {
+        let invalid_bet = GameError::InvalidBet {
+            bet: 0,
+            bet_min: 1,
+            bet_max: 10,
+        };
+        let config = GameError::from(ConfigError::Paylines);
+
+        assert_eq!(invalid_bet.code(), 1001);
+        assert_eq!(invalid_bet.category(), ErrorCategory::Validation);
+        assert_eq!(GameError::LowBalance.code(), 2001);
+        assert_eq!(GameError::LowBalance.category(), ErrorCategory::Funds);
+        assert_eq!(config.code(), 5002);
+        assert_eq!(config.category(), ErrorCategory::Config);
+    }
+
+    #[test]
+    fn source_chaining() {
+        let serialization = GameError::from(serde_json::from_str::<u32>("x").unwrap_err());
+        let config = GameError::from(ConfigError::Reels(1));
+
+        assert_eq!(serialization.category(), ErrorCategory::Storage);
+        assert!(serialization.source().is_some());
+        assert_eq!(
+            config.source().unwrap().to_string(),
+            ConfigError::Reels(1).to_string()
+        );
+        assert!(GameError::LowBalance.source().is_none());
+    }
+
+    #[test]
+    fn only_transient_errors_are_retryable() {
+        let rng = GameError::from(rand::Error::from(std::num::NonZeroU32::new(1).unwrap()));
+        let timeout = GameError::Wallet {
+            source: "the wallet service timed out".into(),
+            retry_after: Some(Duration::from_secs(1)),
+        };
+        let rejected = GameError::Wallet {
+            source: "the account is closed".into(),
+            retry_after: None,
+        };
+
+        assert!(rng.is_retryable());
+        assert!(rng.retry_after().is_some());
+        assert!(timeout.is_retryable());
+        assert_eq!(timeout.retry_after(), Some(Duration::from_secs(1)));
+        assert!(!rejected.is_retryable());
+        assert!(GameError::from(io::Error::from(io::ErrorKind::TimedOut)).is_retryable());
+        assert!(GameError::from(io::Error::from(io::ErrorKind::Interrupted)).is_retryable());
+        assert!(GameError::from(io::Error::from(io::ErrorKind::WouldBlock)).is_retryable());
+        assert!(!GameError::from(io::Error::from(io::ErrorKind::NotFound)).is_retryable());
+        assert_eq!(rejected.category(), ErrorCategory::Storage);
+        assert!(!GameError::LowBalance.is_retryable());
+        assert_eq!(GameError::LowBalance.retry_after(), None);
+    }
+}
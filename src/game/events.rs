@@ -0,0 +1,116 @@
+use crate::game::invariant::Violation;
+use crate::game::spin::SpinResult;
+use serde_derive::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::mpsc::Sender;
+
+/// A win of at least `BIG_WIN` times the total bet fires [`GameEvent::BigWin`].
+pub const BIG_WIN: u32 = 20;
+
+/// A change of the [`Game`] pushed to the [`EventListener`]s.
+///
+/// [`Game`]: crate::game::Game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GameEvent {
+    /// A spin is played, `bet` is the total bet, 0 for a free spin
+    SpinStarted { bet: u32 },
+    /// A spin is over
+    SpinCompleted(SpinResult),
+    /// A spin won at least [`BIG_WIN`] times the total bet
+    BigWin { win: u32, bet: u32 },
+    /// The balance changed
+    BalanceChanged { previous: u32, current: u32 },
+    /// The bet per line changed
+    BetChanged { previous: u32, current: u32 },
+    /// A line won the progressive jackpot
+    JackpotHit { amount: u32 },
+    /// A spin broke a game invariant, fired with [`InvariantMode::Log`]
+    ///
+    /// [`InvariantMode::Log`]: crate::game::invariant::InvariantMode::Log
+    InvariantViolated(Violation),
+}
+
+/// Receives the events of a [`Game`], see [`Game::add_listener`].
+///
+/// A [`Sender`] is a listener too, so the events can be consumed as a channel.
+///
+/// # Examples
+///
+/// ```
+/// # use slot_machine::game::events::GameEvent;
+/// # use slot_machine::game::paytable::Paytable;
+/// # use slot_machine::game::Game;
+/// # use std::sync::mpsc;
+/// let mut game = Game::new(1000, 1, 1, 100, Paytable::default()).unwrap();
+/// let (sender, receiver) = mpsc::channel();
+/// game.add_listener(sender);
+///
+/// game.spin().unwrap();
+///
+/// assert_eq!(receiver.recv().unwrap(), GameEvent::SpinStarted { bet: 1 });
+/// ```
+///
+/// [`Game`]: crate::game::Game
+/// [`Game::add_listener`]: crate::game::Game::add_listener
+pub trait EventListener {
+    /// Called for every event, in the order the changes happen
+    fn on_event(&mut self, event: &GameEvent);
+}
+
+impl EventListener for Sender<GameEvent> {
+    // A disconnected receiver is not an error of the game, the event is dropped.
+    fn on_event(&mut self, event: &GameEvent) {
+        let _ = self.send(event.clone());
+    }
+}
+
+/// Listeners of a [`Game`].
+///
+/// Listeners belong to the running game: they are neither saved nor cloned,
+/// so copies made by simulations and replays do not fire events.
+///
+/// [`Game`]: crate::game::Game
+#[derive(Default)]
+pub(crate) struct Listeners(Vec<Box<dyn EventListener + Send + Sync>>);
+
+impl Listeners {
+    // Adds the `listener`.
+    pub(crate) fn push(&mut self, listener: Box<dyn EventListener + Send + Sync>) {
+        self.0.push(listener);
+    }
+
+    // Removes all listeners.
+    pub(crate) fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    // Returns true if there is at least one listener.
+    pub(crate) fn is_active(&self) -> bool {
+        !self.0.is_empty()
+    }
+
+    // Passes the `event` to every listener.
+    pub(crate) fn emit(&mut self, event: GameEvent) {
+        for listener in self.0.iter_mut() {
+            listener.on_event(&event);
+        }
+    }
+}
+
+impl Clone for Listeners {
+    fn clone(&self) -> Self {
+        Listeners::default()
+    }
+}
+
+impl PartialEq for Listeners {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl fmt::Debug for Listeners {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Listeners({})", self.0.len())
+    }
+}
@@ -0,0 +1,270 @@
+use crate::game::error::GameError;
+use crate::game::reel::ReelStrip;
+use crate::game::window::Window;
+use crate::game::NUM_ROWS;
+use hmac::{Hmac, Mac};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde_derive::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+/// Provably fair spins: the stops are derived from a secret server seed, a client seed and a nonce.
+///
+/// Before playing, the player receives the [`commitment`], the SHA-256 hash of the server seed,
+/// and may choose the client seed. Every spin uses the next nonce, the stop on each reel is
+/// derived from `HMAC-SHA256(server seed, "client seed:nonce:round")`. After the server seed is
+/// revealed with [`rotate`] the player checks every spin with [`verify`].
+///
+/// # Examples
+///
+/// ```
+/// # use slot_machine::game::fairness::{self, ProvablyFair};
+/// # use slot_machine::game::paytable::Paytable;
+/// # use slot_machine::game::Game;
+/// let mut game = Game::new(1000, 1, 1, 100, Paytable::default()).unwrap();
+/// let mut fairness = ProvablyFair::new("lucky player").unwrap();
+/// let commitment = fairness.commitment();
+///
+/// let nonce = fairness.nonce();
+/// let result = game.spin_fair(&mut fairness).unwrap();
+/// let server_seed = fairness.rotate().unwrap();
+///
+/// assert!(fairness::verify(
+///     &commitment,
+///     &server_seed,
+///     "lucky player",
+///     nonce,
+///     game.reels(),
+///     &result.window,
+/// ));
+/// ```
+///
+/// [`commitment`]: ProvablyFair::commitment
+/// [`rotate`]: ProvablyFair::rotate
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProvablyFair {
+    server_seed: String,
+    client_seed: String,
+    nonce: u64,
+}
+
+impl ProvablyFair {
+    /// Creates new [`ProvablyFair`] with a random server seed and the `client_seed`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GameError::Rng`] if the operating system entropy is unavailable.
+    pub fn new(client_seed: &str) -> Result<Self, GameError> {
+        Ok(ProvablyFair {
+            server_seed: server_seed()?,
+            client_seed: client_seed.to_owned(),
+            nonce: 0,
+        })
+    }
+
+    /// Returns the SHA-256 hash of the server seed in hex, published before the spins
+    pub fn commitment(&self) -> String {
+        commitment(&self.server_seed)
+    }
+
+    /// Returns the client seed
+    pub fn client_seed(&self) -> &str {
+        &self.client_seed
+    }
+
+    /// Client seed setter, the player may change it at any time.
+    pub fn set_client_seed(&mut self, client_seed: &str) {
+        self.client_seed = client_seed.to_owned();
+    }
+
+    /// Returns the nonce of the next spin
+    pub fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    /// Reveals the server seed and replaces it with a new one, the nonce starts from 0.
+    ///
+    /// Returns the revealed server seed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GameError::Rng`] if the operating system entropy is unavailable,
+    /// the server seed is kept secret in this case.
+    pub fn rotate(&mut self) -> Result<String, GameError> {
+        let next = server_seed()?;
+        self.nonce = 0;
+
+        Ok(std::mem::replace(&mut self.server_seed, next))
+    }
+
+    // Creates the state of a spin with the revealed `server_seed`, used to replay the spin.
+    pub(crate) fn revealed(server_seed: &str, client_seed: &str, nonce: u64) -> Self {
+        ProvablyFair {
+            server_seed: server_seed.to_owned(),
+            client_seed: client_seed.to_owned(),
+            nonce,
+        }
+    }
+
+    // Derives the stops of the next spin and advances the nonce.
+    pub(crate) fn next_stops(&mut self, reels: &[ReelStrip]) -> Vec<usize> {
+        let stops = stops(&self.server_seed, &self.client_seed, self.nonce, reels);
+        self.nonce += 1;
+
+        stops
+    }
+}
+
+impl fmt::Debug for ProvablyFair {
+    // The server seed is secret until revealed, so it is never printed.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProvablyFair")
+            .field("commitment", &self.commitment())
+            .field("client_seed", &self.client_seed)
+            .field("nonce", &self.nonce)
+            .finish()
+    }
+}
+
+/// Returns the SHA-256 hash of the `server_seed` in hex.
+pub fn commitment(server_seed: &str) -> String {
+    hex(&Sha256::digest(server_seed.as_bytes()))
+}
+
+/// Derives the stop index on each of the `reels` for the spin with the `nonce`.
+///
+/// Each reel takes 4 bytes of `HMAC-SHA256(server_seed, "client_seed:nonce:round")`,
+/// the round starts from 0 and grows every 8 reels. The bytes are mapped onto the
+/// total weight of the reel, see [`ReelStrip::index_of`].
+pub fn stops(server_seed: &str, client_seed: &str, nonce: u64, reels: &[ReelStrip]) -> Vec<usize> {
+    let numbers_per_round = 8;
+
+    reels
+        .chunks(numbers_per_round)
+        .enumerate()
+        .flat_map(|(round, chunk)| {
+            let mut mac = Hmac::<Sha256>::new_from_slice(server_seed.as_bytes())
+                .expect("HMAC accepts keys of any length");
+            mac.update(format!("{}:{}:{}", client_seed, nonce, round).as_bytes());
+            let bytes = mac.finalize().into_bytes();
+
+            chunk
+                .iter()
+                .zip(bytes.chunks(4))
+                .map(|(reel, x)| {
+                    let number = u32::from_be_bytes([x[0], x[1], x[2], x[3]]);
+                    let number = (u64::from(number) * u64::from(reel.total_weight())) >> 32;
+
+                    reel.index_of(number as u32).unwrap()
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Checks that the revealed `server_seed` matches the `commitment` and that the spin with
+/// the `nonce` on the `reels` shows the `window`.
+///
+/// See [`ProvablyFair`].
+pub fn verify(
+    commitment: &str,
+    server_seed: &str,
+    client_seed: &str,
+    nonce: u64,
+    reels: &[ReelStrip],
+    window: &Window,
+) -> bool {
+    if self::commitment(server_seed) != commitment {
+        return false;
+    }
+
+    let expected = Window::from_columns(
+        reels
+            .iter()
+            .zip(stops(server_seed, client_seed, nonce, reels))
+            .map(|(reel, stop)| reel.window(stop, NUM_ROWS))
+            .collect(),
+    );
+
+    expected == *window
+}
+
+// Generates a random server seed of 32 bytes in hex.
+fn server_seed() -> Result<String, GameError> {
+    let mut bytes = [0; 32];
+    OsRng.try_fill_bytes(&mut bytes)?;
+
+    Ok(hex(&bytes))
+}
+
+// Formats the `bytes` as lowercase hex.
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|x| format!("{:02x}", x)).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::symbol::Symbol::Wild;
+    use crate::game::NUM_REELS;
+
+    #[test]
+    fn commitment_sha256() {
+        assert_eq!(
+            commitment("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn stops_deterministic() {
+        let reels = vec![ReelStrip::default(); 10];
+
+        let a = stops("server", "client", 7, &reels);
+        let b = stops("server", "client", 7, &reels);
+
+        assert_eq!(a, b);
+        assert_eq!(a.len(), 10);
+        assert!(a.iter().all(|&x| x < ReelStrip::default().len()));
+        assert_ne!(a, stops("server", "client", 8, &reels));
+        assert_ne!(a, stops("server", "other", 7, &reels));
+    }
+
+    #[test]
+    fn verify_rejects_tampering() {
+        let reels = vec![ReelStrip::default(); NUM_REELS];
+        let mut fairness = ProvablyFair::new("client").unwrap();
+        let commitment = fairness.commitment();
+
+        let window = Window::from_columns(
+            reels
+                .iter()
+                .zip(fairness.next_stops(&reels))
+                .map(|(reel, stop)| reel.window(stop, NUM_ROWS))
+                .collect(),
+        );
+        let server_seed = fairness.rotate().unwrap();
+
+        assert_eq!(fairness.nonce(), 0);
+        assert_ne!(fairness.commitment(), commitment);
+        assert!(verify(
+            &commitment,
+            &server_seed,
+            "client",
+            0,
+            &reels,
+            &window
+        ));
+        assert!(!verify(&commitment, "guess", "client", 0, &reels, &window));
+        let forged = Window::new(NUM_REELS, NUM_ROWS, vec![Wild; NUM_REELS * NUM_ROWS]);
+        assert!(!verify(
+            &commitment,
+            &server_seed,
+            "client",
+            0,
+            &reels,
+            &forged
+        ));
+    }
+}
@@ -0,0 +1,88 @@
+use crate::game::payline::Payline;
+use crate::game::paytable::{Combination, Paytable, Rule, ScatterRule};
+use crate::game::reel::{ReelStrip, Stop};
+use std::mem::{size_of, size_of_val};
+
+/// Approximate number of bytes used by the configuration of a [`Game`].
+///
+/// Counts the heap memory of the configuration, the size of the [`Game`] itself is
+/// `std::mem::size_of::<Game>()`.
+///
+/// [`Game`]: crate::game::Game
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryFootprint {
+    /// Reel strips
+    pub reels: usize,
+    /// Paytable rules and their names
+    pub paytable: usize,
+    /// Paylines
+    pub paylines: usize,
+    /// Precomputed payout lookup table, 0 if the game has none
+    pub lookup: usize,
+}
+
+impl MemoryFootprint {
+    /// Returns the total number of bytes
+    pub fn total(&self) -> usize {
+        self.reels + self.paytable + self.paylines + self.lookup
+    }
+}
+
+// Returns the number of bytes used by the `reels`.
+pub(crate) fn reels(reels: &[ReelStrip]) -> usize {
+    reels
+        .iter()
+        .map(|x| size_of::<ReelStrip>() + x.len() * size_of::<Stop>())
+        .sum()
+}
+
+// Returns the number of bytes used by the rules of the `paytable`.
+pub(crate) fn paytable(paytable: &Paytable) -> usize {
+    paytable
+        .rules()
+        .iter()
+        .map(|rule| {
+            let combination = match &rule.combination {
+                Combination::AnyOf(symbols) | Combination::CountOf { symbols, .. } => {
+                    size_of_val(symbols.as_slice())
+                }
+                Combination::Count { .. } | Combination::FromLeft { .. } => 0,
+            };
+
+            size_of::<Rule>() + rule.name.len() + combination
+        })
+        .chain(
+            paytable
+                .scatters()
+                .iter()
+                .map(|rule| size_of::<ScatterRule>() + rule.name.len()),
+        )
+        .sum()
+}
+
+// Returns the number of bytes used by the `paylines`.
+pub(crate) fn paylines(paylines: &[Payline]) -> usize {
+    paylines
+        .iter()
+        .map(|x| size_of::<Payline>() + size_of_val(x.rows()))
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::Game;
+
+    #[test]
+    fn default_game() {
+        let game = Game::new(1000, 1, 1, 10, Paytable::default()).unwrap();
+        let footprint = game.memory_footprint();
+
+        assert_eq!(footprint.reels, reels(game.reels()));
+        assert!(footprint.lookup >= 22 * 22 * 22);
+        assert_eq!(
+            footprint.total(),
+            footprint.reels + footprint.paytable + footprint.paylines + footprint.lookup
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use crate::game::window::Window;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::{Duration, SystemTime};
+
+/// A spin recorded in the [`History`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpinRecord {
+    /// When the spin was played
+    pub timestamp: SystemTime,
+    /// Total bet of the spin
+    pub bet: u32,
+    /// Symbols visible on the reels
+    pub window: Window,
+    /// The amount of the win
+    pub win: u32,
+    /// Balance after the spin
+    pub credits: u32,
+}
+
+/// The most recent spins of a [`Game`], up to a fixed capacity.
+///
+/// When the history is full the oldest spin is dropped.
+///
+/// # Examples
+///
+/// ```
+/// # use slot_machine::game::paytable::Paytable;
+/// # use slot_machine::game::Game;
+/// let mut game = Game::new(1000, 1, 1, 100, Paytable::default()).unwrap();
+/// for _ in 0..5 {
+///     game.spin().unwrap();
+/// }
+///
+/// assert_eq!(game.history().last(3).count(), 3);
+/// assert_eq!(game.history().total_wagered(), 5);
+/// ```
+///
+/// [`Game`]: crate::game::Game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct History {
+    capacity: usize,
+    records: VecDeque<SpinRecord>,
+}
+
+impl History {
+    /// Default number of spins kept in the history.
+    pub const DEFAULT_CAPACITY: usize = 100;
+
+    /// Creates an empty [`History`] keeping up to `capacity` spins.
+    pub fn new(capacity: usize) -> Self {
+        History {
+            capacity,
+            records: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the maximum number of spins kept
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Capacity setter, drops the oldest spins that do not fit.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.truncate();
+    }
+
+    /// Returns the number of recorded spins
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns `true` if no spins are recorded
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Records the spin, dropping the oldest one if the history is full.
+    pub fn push(&mut self, record: SpinRecord) {
+        self.records.push_back(record);
+        self.truncate();
+    }
+
+    /// Removes all recorded spins
+    pub fn clear(&mut self) {
+        self.records.clear();
+    }
+
+    /// Removes the spins played more than `max_age` ago and returns how many were removed.
+    ///
+    /// The capacity keeps the last N spins, `prune` keeps the spins of the last N days.
+    pub fn prune(&mut self, max_age: Duration) -> usize {
+        let len = self.records.len();
+        // A spin from the future is kept, the clock may have been set back.
+        let expired = |x: &SpinRecord| x.timestamp.elapsed().is_ok_and(|x| x > max_age);
+
+        while self.records.front().is_some_and(expired) {
+            self.records.pop_front();
+        }
+
+        len - self.records.len()
+    }
+
+    /// Returns the recorded spins from the oldest to the newest
+    pub fn iter(&self) -> impl Iterator<Item = &SpinRecord> {
+        self.records.iter()
+    }
+
+    /// Returns up to `n` most recent spins, the newest first
+    pub fn last(&self, n: usize) -> impl Iterator<Item = &SpinRecord> {
+        self.records.iter().rev().take(n)
+    }
+
+    /// Returns the sum of the bets of the recorded spins
+    pub fn total_wagered(&self) -> u64 {
+        self.records.iter().map(|x| u64::from(x.bet)).sum()
+    }
+
+    /// Returns the sum of the wins of the recorded spins
+    pub fn total_won(&self) -> u64 {
+        self.records.iter().map(|x| u64::from(x.win)).sum()
+    }
+
+    /// Returns the recorded spin with the biggest win, the earliest one if there are several
+    pub fn biggest_win(&self) -> Option<&SpinRecord> {
+        self.records
+            .iter()
+            .rev()
+            .max_by_key(|x| x.win)
+            .filter(|x| x.win > 0)
+    }
+
+    // Drops the oldest spins exceeding the capacity.
+    fn truncate(&mut self) {
+        while self.records.len() > self.capacity {
+            self.records.pop_front();
+        }
+    }
+}
+
+impl Default for History {
+    /// Empty history keeping [`History::DEFAULT_CAPACITY`] spins.
+    fn default() -> Self {
+        History::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::symbol::Symbol;
+
+    fn record(bet: u32, win: u32) -> SpinRecord {
+        SpinRecord {
+            timestamp: SystemTime::now(),
+            bet,
+            window: Window::new(1, 1, vec![Symbol::Blank]),
+            win,
+            credits: 0,
+        }
+    }
+
+    #[test]
+    fn ring_buffer() {
+        let mut history = History::new(2);
+        history.push(record(1, 0));
+        history.push(record(2, 0));
+        history.push(record(3, 0));
+
+        let bets: Vec<u32> = history.last(5).map(|x| x.bet).collect();
+
+        assert_eq!(bets, vec![3, 2]);
+        assert_eq!(history.total_wagered(), 5);
+    }
+
+    #[test]
+    fn biggest_win() {
+        let mut history = History::new(10);
+        assert!(history.biggest_win().is_none());
+
+        history.push(record(1, 0));
+        assert!(history.biggest_win().is_none());
+
+        history.push(record(2, 12));
+        history.push(record(3, 12));
+        history.push(record(4, 3));
+
+        assert_eq!(history.biggest_win().unwrap().bet, 2);
+        assert_eq!(history.total_won(), 27);
+    }
+
+    #[test]
+    fn set_capacity() {
+        let mut history = History::new(3);
+        for bet in 1..=3 {
+            history.push(record(bet, 0));
+        }
+
+        history.set_capacity(1);
+
+        assert_eq!(history.len(), 1);
+        assert_eq!(history.iter().next().unwrap().bet, 3);
+    }
+
+    #[test]
+    fn prune() {
+        let day = Duration::from_secs(24 * 60 * 60);
+        let mut history = History::new(10);
+        for days in [3, 2, 0].iter() {
+            let mut record = record(*days, 0);
+            record.timestamp -= day * *days;
+            history.push(record);
+        }
+
+        assert_eq!(history.prune(day), 2);
+        assert_eq!(history.prune(day), 0);
+        assert_eq!(history.iter().next().unwrap().bet, 0);
+    }
+}
@@ -0,0 +1,349 @@
+use crate::game::bonus::GameMode;
+use crate::game::events::{GameEvent, Listeners};
+use crate::game::paytable::Paytable;
+use crate::game::snapshot::GameSnapshot;
+use crate::game::spin::SpinResult;
+use serde_derive::{Deserialize, Serialize};
+use std::fmt;
+
+/// Defines what happens when a spin breaks one of the game invariants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InvariantMode {
+    /// Invariants are not checked
+    Off,
+    /// Violations are reported to the listeners as [`GameEvent::InvariantViolated`]
+    Log,
+    /// Violations cause a panic
+    Panic,
+}
+
+impl Default for InvariantMode {
+    /// [`InvariantMode::Panic`] in debug builds and [`InvariantMode::Off`] in release builds.
+    fn default() -> Self {
+        if cfg!(debug_assertions) {
+            InvariantMode::Panic
+        } else {
+            InvariantMode::Off
+        }
+    }
+}
+
+/// A broken game invariant.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Violation {
+    /// Balance after the round is not `credits - bet + win`
+    Balance { expected: u32, actual: u32 },
+    /// The win does not match the payout of the winning lines
+    Win { expected: u32, actual: u32 },
+    /// The bet is outside of the bet limits
+    Bet {
+        bet: u32,
+        bet_min: u32,
+        bet_max: u32,
+    },
+    /// A free spin took a bet from the balance or a base game spin multiplied the wins
+    FreeSpin { bet: u32, multiplier: u32 },
+    /// The mode changed in a way no spin can change it, e.g. free spins with no spins left
+    Mode { before: GameMode, after: GameMode },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::Balance { expected, actual } => write!(
+                f,
+                "balance is not conserved: expected {} credits, got {}",
+                expected, actual
+            ),
+            Violation::Win { expected, actual } => write!(
+                f,
+                "win does not match the payout: expected {}, got {}",
+                expected, actual
+            ),
+            Violation::Bet {
+                bet,
+                bet_min,
+                bet_max,
+            } => write!(f, "bet {} is outside of {}..={}", bet, bet_min, bet_max),
+            Violation::FreeSpin { bet, multiplier } => write!(
+                f,
+                "a spin took a bet of {} with the multiplier {} in a wrong mode",
+                bet, multiplier
+            ),
+            Violation::Mode { before, after } => {
+                write!(f, "mode cannot change from {} to {}", before, after)
+            }
+        }
+    }
+}
+
+/// Checks the invariants of a single round.
+///
+/// `before` and `after` are the meters around the round, `result` is the outcome of the round
+/// and `paytable` is the paytable of the game. Progressive jackpot and scatter wins are not
+/// checked against the paytable. A free spin must not take a bet, and free spins are used up
+/// one per spin unless the scatters award more.
+/// Returns all violated invariants, the list is empty if the round is consistent.
+pub fn check_spin(
+    before: &GameSnapshot,
+    after: &GameSnapshot,
+    result: &SpinResult,
+    paytable: &Paytable,
+) -> Vec<Violation> {
+    let mut violations = Vec::new();
+
+    let expected = before
+        .credits
+        .saturating_sub(result.bet)
+        .saturating_add(result.win);
+    if expected != after.credits {
+        violations.push(Violation::Balance {
+            expected,
+            actual: after.credits,
+        });
+    }
+
+    // Wins saturate at `u32::MAX` like in the spin itself.
+    let scatter = result.scatter.as_ref().map_or(0, |x| x.win);
+    let expected = result
+        .lines
+        .iter()
+        .map(|x| {
+            if x.jackpot {
+                return x.win;
+            }
+
+            // A line may win once per rule with `PayPolicy::All`, so the rule is found by name.
+            let mut pays = 0;
+            paytable.for_each_win(&x.symbols, |rule, rule_pays| {
+                if rule.name == x.combination {
+                    pays = rule_pays;
+                }
+            });
+            pays.saturating_mul(after.bet)
+                .saturating_mul(result.multiplier)
+        })
+        .fold(scatter, u32::saturating_add);
+    let actual = result
+        .lines
+        .iter()
+        .map(|x| x.win)
+        .fold(scatter, u32::saturating_add);
+    if expected != actual || actual != result.win {
+        violations.push(Violation::Win {
+            expected,
+            actual: result.win,
+        });
+    }
+
+    let free = matches!(before.mode, GameMode::FreeSpins { .. });
+    if (free && result.bet > 0) || (!free && result.multiplier != 1) {
+        violations.push(Violation::FreeSpin {
+            bet: result.bet,
+            multiplier: result.multiplier,
+        });
+    }
+
+    let consistent = match (before.mode, after.mode) {
+        (_, GameMode::FreeSpins { remaining: 0 }) => false,
+        (GameMode::FreeSpins { remaining }, GameMode::BaseGame) => remaining == 1,
+        (GameMode::FreeSpins { remaining }, GameMode::FreeSpins { remaining: next }) => {
+            next + 1 >= remaining
+        }
+        (GameMode::BaseGame, _) => true,
+    };
+    if !consistent || after.mode != result.mode {
+        violations.push(Violation::Mode {
+            before: before.mode,
+            after: after.mode,
+        });
+    }
+
+    if after.bet < after.bet_min || after.bet > after.bet_max {
+        violations.push(Violation::Bet {
+            bet: after.bet,
+            bet_min: after.bet_min,
+            bet_max: after.bet_max,
+        });
+    }
+
+    violations
+}
+
+// Reports `violations` according to `mode`, logged violations go to the `listeners`.
+pub(crate) fn report(mode: InvariantMode, violations: Vec<Violation>, listeners: &mut Listeners) {
+    match mode {
+        InvariantMode::Off => {}
+        InvariantMode::Log => {
+            for violation in violations {
+                listeners.emit(GameEvent::InvariantViolated(violation));
+            }
+        }
+        InvariantMode::Panic => {
+            if let Some(violation) = violations.first() {
+                panic!("Invariant violated: {}", violation);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::bonus::GameMode;
+    use crate::game::payline::Payline;
+    use crate::game::symbol::Symbol;
+    use crate::game::symbol::Symbol::*;
+    use crate::game::window::Window;
+    use crate::game::NUM_REELS;
+
+    fn snapshot(credits: u32, bet: u32, win: u32) -> GameSnapshot {
+        GameSnapshot {
+            credits,
+            bet,
+            bet_min: 1,
+            bet_max: 10,
+            win,
+            mode: GameMode::BaseGame,
+            jackpot_pool: None,
+        }
+    }
+
+    fn result(symbols: &[Symbol], bet: u32, win: u32, credits: u32) -> SpinResult {
+        let window = Window::new(symbols.len(), 1, symbols.to_vec());
+        let lines =
+            Paytable::default().evaluate_lines(&window, &[Payline::horizontal(NUM_REELS, 0)], bet);
+
+        SpinResult {
+            window,
+            bet,
+            win,
+            lines,
+            scatter: None,
+            multiplier: 1,
+            mode: GameMode::BaseGame,
+            credits,
+        }
+    }
+
+    #[test]
+    fn check_spin_consistent() {
+        let before = snapshot(100, 2, 0);
+        let after = snapshot(104, 2, 6);
+        let result = result(&[Cherry, Blank, Bar], 2, 6, 104);
+
+        assert!(check_spin(&before, &after, &result, &Paytable::default()).is_empty())
+    }
+
+    #[test]
+    fn check_spin_balance_not_conserved() {
+        let before = snapshot(100, 2, 0);
+        let after = snapshot(100, 2, 0);
+        let result = result(&[Blank, Blank, Blank], 2, 0, 100);
+
+        assert_eq!(
+            check_spin(&before, &after, &result, &Paytable::default()),
+            vec![Violation::Balance {
+                expected: 98,
+                actual: 100
+            }]
+        )
+    }
+
+    #[test]
+    fn check_spin_win_mismatch() {
+        let before = snapshot(100, 1, 0);
+        let after = snapshot(101, 1, 2);
+        let mut result = result(&[Cherry, Blank, Blank], 1, 3, 101);
+        result.win = 2;
+
+        assert_eq!(
+            check_spin(&before, &after, &result, &Paytable::default()),
+            vec![Violation::Win {
+                expected: 3,
+                actual: 2
+            }]
+        )
+    }
+
+    #[test]
+    fn check_spin_free_spins() {
+        let free = |remaining| GameMode::FreeSpins { remaining };
+        let spin = |before_mode, after_mode, bet| {
+            let before = GameSnapshot {
+                mode: before_mode,
+                ..snapshot(100, 1, 0)
+            };
+            let after = GameSnapshot {
+                mode: after_mode,
+                ..snapshot(100 - bet, 1, 0)
+            };
+            let mut result = result(&[Blank, Blank, Blank], bet, 0, 100 - bet);
+            result.mode = after_mode;
+            if before_mode != GameMode::BaseGame {
+                result.multiplier = 2;
+            }
+
+            check_spin(&before, &after, &result, &Paytable::default())
+        };
+
+        assert!(spin(GameMode::BaseGame, free(10), 1).is_empty());
+        assert!(spin(free(5), free(4), 0).is_empty());
+        assert!(spin(free(5), free(14), 0).is_empty());
+        assert!(spin(free(1), GameMode::BaseGame, 0).is_empty());
+        assert_eq!(
+            spin(free(5), free(4), 1),
+            vec![Violation::FreeSpin {
+                bet: 1,
+                multiplier: 2
+            }]
+        );
+        assert_eq!(
+            spin(free(5), GameMode::BaseGame, 0),
+            vec![Violation::Mode {
+                before: free(5),
+                after: GameMode::BaseGame
+            }]
+        );
+        assert_eq!(
+            spin(free(5), free(3), 0),
+            vec![Violation::Mode {
+                before: free(5),
+                after: free(3)
+            }]
+        );
+    }
+
+    #[test]
+    fn report_log() {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let mut listeners = Listeners::default();
+        listeners.push(Box::new(sender));
+        let violation = Violation::Bet {
+            bet: 20,
+            bet_min: 1,
+            bet_max: 10,
+        };
+
+        report(InvariantMode::Log, vec![violation.clone()], &mut listeners);
+
+        assert_eq!(
+            receiver.try_recv(),
+            Ok(GameEvent::InvariantViolated(violation))
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn report_panic() {
+        report(
+            InvariantMode::Panic,
+            vec![Violation::Bet {
+                bet: 20,
+                bet_min: 1,
+                bet_max: 10,
+            }],
+            &mut Listeners::default(),
+        );
+    }
+}
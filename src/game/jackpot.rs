@@ -0,0 +1,129 @@
+use crate::game::symbol::Symbol;
+use serde_derive::{Deserialize, Serialize};
+
+/// Progressive jackpot.
+///
+/// A percentage of every bet feeds the pool. The first payline of a spin showing [`Symbol::Jackpot`]
+/// on every reel pays the whole pool instead of the paytable, then the pool starts again from
+/// the seed amount. The pool is paid as is, the free spins multiplier does not apply to it,
+/// and further jackpot paylines of the same spin pay the paytable.
+///
+/// # Examples
+///
+/// ```
+/// # use slot_machine::game::jackpot::Jackpot;
+/// let mut jackpot = Jackpot::new(1000, 5);
+/// jackpot.contribute(100);
+///
+/// assert_eq!(jackpot.pool(), 1005);
+/// assert_eq!(jackpot.take(), 1005);
+/// assert_eq!(jackpot.pool(), 1000);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Jackpot {
+    /// The amount the pool starts from
+    seed: u32,
+    /// Percentage of every bet added to the pool
+    rate: u32,
+    /// Current size of the pool
+    pool: u32,
+    /// Hundredths of a credit contributed but not yet added to the pool
+    remainder: u32,
+}
+
+impl Jackpot {
+    /// Creates new [`Jackpot`], the pool starts from `seed` and grows by `rate` percent of every bet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is greater than 100.
+    pub fn new(seed: u32, rate: u32) -> Self {
+        assert!(rate <= 100, "`rate` must not be greater than 100!");
+
+        Jackpot {
+            seed,
+            rate,
+            pool: seed,
+            remainder: 0,
+        }
+    }
+
+    /// Returns the amount the pool starts from
+    pub fn seed(&self) -> u32 {
+        self.seed
+    }
+
+    /// Returns the percentage of every bet added to the pool
+    pub fn rate(&self) -> u32 {
+        self.rate
+    }
+
+    /// Contribution rate setter, takes effect from the next bet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is greater than 100.
+    pub fn set_rate(&mut self, rate: u32) {
+        assert!(rate <= 100, "`rate` must not be greater than 100!");
+
+        self.rate = rate;
+    }
+
+    /// Returns the current size of the pool
+    pub fn pool(&self) -> u32 {
+        self.pool
+    }
+
+    /// Adds [`Jackpot::rate`] percent of the `bet` to the pool.
+    ///
+    /// Fractions of a credit are carried over to the next contribution, so no money is lost.
+    pub fn contribute(&mut self, bet: u32) {
+        let hundredths = u64::from(bet) * u64::from(self.rate) + u64::from(self.remainder);
+
+        self.pool = self.pool.saturating_add((hundredths / 100) as u32);
+        self.remainder = (hundredths % 100) as u32;
+    }
+
+    /// Returns `true` if the `symbols` on a payline win the jackpot
+    pub fn is_hit(&self, symbols: &[Symbol]) -> bool {
+        !symbols.is_empty() && symbols.iter().all(|&x| x == Symbol::Jackpot)
+    }
+
+    /// Pays out the pool and resets it to the seed amount, returns the amount paid.
+    pub fn take(&mut self) -> u32 {
+        std::mem::replace(&mut self.pool, self.seed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn contribute_carries_fractions() {
+        let mut jackpot = Jackpot::new(0, 1);
+
+        for _ in 0..99 {
+            jackpot.contribute(1);
+        }
+        assert_eq!(jackpot.pool(), 0);
+
+        jackpot.contribute(1);
+        assert_eq!(jackpot.pool(), 1);
+    }
+
+    #[test]
+    fn is_hit() {
+        let jackpot = Jackpot::new(100, 1);
+
+        assert!(jackpot.is_hit(&[Symbol::Jackpot; 3]));
+        assert!(!jackpot.is_hit(&[Symbol::Jackpot, Symbol::Jackpot, Symbol::Seven]));
+        assert!(!jackpot.is_hit(&[]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_rate_too_big() {
+        Jackpot::new(100, 101);
+    }
+}
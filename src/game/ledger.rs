@@ -0,0 +1,180 @@
+use serde_derive::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Reason of a balance change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TransactionKind {
+    /// The bet of a spin was debited
+    Bet,
+    /// The win of a spin was credited
+    Win,
+    /// The player added credits
+    Deposit,
+    /// The player withdrew credits
+    CashOut,
+    /// The bet of a spin whose win could not be credited was returned
+    Refund,
+}
+
+/// A single balance change recorded in the [`Ledger`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Transaction {
+    /// Sequential number of the transaction, starting from 1
+    pub id: u64,
+    /// Reason of the change
+    pub kind: TransactionKind,
+    /// The amount of credits debited or credited
+    pub amount: u32,
+    /// Balance after the transaction
+    pub balance: u32,
+}
+
+/// The most recent balance changes of a [`Game`] in the order they happened, up to a fixed capacity.
+///
+/// Transactions are only appended by the game and never changed, so the ledger is an audit
+/// trail of the balance. When the ledger is full the oldest transaction is dropped, the ids
+/// keep counting and the most recent transaction is always kept. Export the transactions
+/// before they rotate out if the whole trail is needed.
+///
+/// # Examples
+///
+/// ```
+/// # use slot_machine::game::ledger::TransactionKind;
+/// # use slot_machine::game::paytable::Paytable;
+/// # use slot_machine::game::Game;
+/// let mut game = Game::new(1000, 1, 1, 100, Paytable::default()).unwrap();
+/// game.spin().unwrap();
+///
+/// let bet = game.ledger().iter().next().unwrap();
+/// assert_eq!(bet.kind, TransactionKind::Bet);
+/// assert_eq!(bet.balance, 999);
+/// ```
+///
+/// [`Game`]: crate::game::Game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Ledger {
+    #[serde(default = "default_capacity")]
+    capacity: usize,
+    transactions: VecDeque<Transaction>,
+}
+
+impl Ledger {
+    /// Default number of transactions kept in the ledger.
+    pub const DEFAULT_CAPACITY: usize = 1000;
+
+    /// Creates an empty [`Ledger`] keeping up to `capacity` transactions.
+    pub fn new(capacity: usize) -> Self {
+        Ledger {
+            capacity,
+            transactions: VecDeque::new(),
+        }
+    }
+
+    /// Returns the maximum number of transactions kept
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Capacity setter, drops the oldest transactions that do not fit.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.truncate();
+    }
+
+    /// Returns the number of recorded transactions
+    pub fn len(&self) -> usize {
+        self.transactions.len()
+    }
+
+    /// Returns `true` if no transactions are recorded
+    pub fn is_empty(&self) -> bool {
+        self.transactions.is_empty()
+    }
+
+    /// Returns the transactions from the oldest to the newest
+    pub fn iter(&self) -> impl Iterator<Item = &Transaction> {
+        self.transactions.iter()
+    }
+
+    /// Returns the most recent transaction
+    pub fn last(&self) -> Option<&Transaction> {
+        self.transactions.back()
+    }
+
+    /// Returns the sum of the amounts of the kept transactions of the `kind`
+    pub fn total(&self, kind: TransactionKind) -> u64 {
+        self.transactions
+            .iter()
+            .filter(|x| x.kind == kind)
+            .map(|x| u64::from(x.amount))
+            .sum()
+    }
+
+    // Appends a transaction with the next id.
+    pub(crate) fn record(&mut self, kind: TransactionKind, amount: u32, balance: u32) {
+        let id = self.last().map_or(1, |x| x.id + 1);
+
+        self.transactions.push_back(Transaction {
+            id,
+            kind,
+            amount,
+            balance,
+        });
+        self.truncate();
+    }
+
+    // Drops the oldest transactions exceeding the capacity, the last one is always kept
+    // because the strict money mode checks the balance against it.
+    pub(crate) fn truncate(&mut self) {
+        while self.transactions.len() > self.capacity.max(1) {
+            self.transactions.pop_front();
+        }
+    }
+}
+
+impl Default for Ledger {
+    /// Empty ledger keeping [`Ledger::DEFAULT_CAPACITY`] transactions.
+    fn default() -> Self {
+        Ledger::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+// The capacity of ledgers saved before the capacity existed.
+fn default_capacity() -> usize {
+    Ledger::DEFAULT_CAPACITY
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn record() {
+        let mut ledger = Ledger::default();
+        ledger.record(TransactionKind::Deposit, 100, 100);
+        ledger.record(TransactionKind::Bet, 5, 95);
+        ledger.record(TransactionKind::Win, 12, 107);
+
+        let ids: Vec<u64> = ledger.iter().map(|x| x.id).collect();
+
+        assert_eq!(ids, vec![1, 2, 3]);
+        assert_eq!(ledger.last().unwrap().balance, 107);
+        assert_eq!(ledger.total(TransactionKind::Bet), 5);
+        assert_eq!(ledger.total(TransactionKind::CashOut), 0);
+    }
+
+    #[test]
+    fn rotate() {
+        let mut ledger = Ledger::new(2);
+        ledger.record(TransactionKind::Deposit, 100, 100);
+        ledger.record(TransactionKind::Bet, 5, 95);
+        ledger.record(TransactionKind::Win, 12, 107);
+
+        let ids: Vec<u64> = ledger.iter().map(|x| x.id).collect();
+        assert_eq!(ids, vec![2, 3]);
+
+        ledger.set_capacity(0);
+        assert_eq!(ledger.len(), 1);
+        assert_eq!(ledger.last().unwrap().id, 3);
+    }
+}
@@ -0,0 +1,179 @@
+use crate::game::payline::Payline;
+use crate::game::paytable::{PayPolicy, Paytable};
+use crate::game::reel::ReelStrip;
+use crate::game::spin::LineWin;
+use crate::game::symbol::Symbol;
+use crate::game::{NUM_REELS, NUM_ROWS};
+use std::mem::size_of_val;
+
+/// Maximum number of entries in a [`LookupTable`], larger machines use the evaluator.
+const MAX_ENTRIES: usize = 1 << 20;
+
+/// Winning rule for every combination of stops of a classic 3-reel machine.
+///
+/// The table is a cache derived from the reels and the paytable: any two tables are equal,
+/// so games can be compared by their meters.
+#[derive(Debug, Clone)]
+pub(crate) struct LookupTable {
+    /// Number of stops on each reel
+    lens: Vec<usize>,
+    /// Index of the winning rule, indexed by the stops of all reels
+    rules: Vec<Option<u16>>,
+}
+
+impl LookupTable {
+    // Precomputes the winning rule for every combination of stops.
+    // Returns `None` if the machine is not a 3-reel machine, the table would be too large,
+    // wilds substitute, as a rule index does not tell whether the win was wild-assisted,
+    // or the paytable pays other than the first matching rule.
+    pub(crate) fn new(paytable: &Paytable, reels: &[ReelStrip]) -> Option<Self> {
+        let lens: Vec<usize> = reels.iter().map(|x| x.len()).collect();
+        let entries = lens.iter().try_fold(1usize, |acc, &x| acc.checked_mul(x))?;
+
+        if reels.len() != NUM_REELS
+            || entries > MAX_ENTRIES
+            || paytable.wild().is_some()
+            || paytable.policy() != PayPolicy::First
+            || paytable.rules().len() > usize::from(u16::MAX)
+        {
+            return None;
+        }
+
+        let mut rules = Vec::with_capacity(entries);
+        for a in reels[0].stops() {
+            for b in reels[1].stops() {
+                for c in reels[2].stops() {
+                    let symbols = [a.symbol, b.symbol, c.symbol];
+                    let rule = paytable
+                        .rules()
+                        .iter()
+                        .position(|x| x.combination.matches(&symbols));
+
+                    rules.push(rule.map(|x| x as u16));
+                }
+            }
+        }
+
+        Some(LookupTable { lens, rules })
+    }
+
+    // Returns the number of bytes used by the table.
+    pub(crate) fn size(&self) -> usize {
+        size_of_val(self.lens.as_slice()) + size_of_val(self.rules.as_slice())
+    }
+
+    // Returns the index of the rule won by the `stops`, one per reel.
+    pub(crate) fn rule(&self, stops: &[usize]) -> Option<usize> {
+        let index = stops
+            .iter()
+            .zip(&self.lens)
+            .fold(0, |acc, (&stop, &len)| acc * len + stop);
+
+        self.rules[index].map(usize::from)
+    }
+
+    // Same as `Paytable::evaluate_lines` for the window of the reels stopped at `stops`.
+    pub(crate) fn evaluate_lines(
+        &self,
+        paytable: &Paytable,
+        reels: &[ReelStrip],
+        stops: &[usize],
+        paylines: &[Payline],
+        bet: u32,
+    ) -> Vec<LineWin> {
+        paylines
+            .iter()
+            .enumerate()
+            .filter_map(|(line, payline)| {
+                let line_stops: Vec<usize> = payline
+                    .rows()
+                    .iter()
+                    .zip(reels.iter().zip(stops))
+                    .map(|(&row, (reel, &stop))| reel.row_index(stop, NUM_ROWS, row))
+                    .collect();
+                let rule = &paytable.rules()[self.rule(&line_stops)?];
+
+                let symbols: Vec<Symbol> = reels
+                    .iter()
+                    .zip(&line_stops)
+                    .map(|(reel, &stop)| reel.symbol(stop))
+                    .collect();
+
+                Some(LineWin {
+                    line,
+                    positions: rule
+                        .combination
+                        .reels(&symbols)
+                        .into_iter()
+                        .map(|reel| payline.position(reel))
+                        .collect(),
+                    symbols,
+                    combination: rule.name.clone(),
+                    win: rule.pays.saturating_mul(bet),
+                    jackpot: false,
+                })
+            })
+            .collect()
+    }
+}
+
+impl PartialEq for LookupTable {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::window::Window;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn matches_evaluator() {
+        let paytable = Paytable::default();
+        let reels = vec![ReelStrip::default(); NUM_REELS];
+        let paylines = vec![
+            Payline::horizontal(NUM_REELS, 0),
+            Payline::horizontal(NUM_REELS, 1),
+            Payline::horizontal(NUM_REELS, 2),
+            Payline::diagonal_down(NUM_REELS),
+            Payline::diagonal_up(NUM_REELS),
+        ];
+        let table = LookupTable::new(&paytable, &reels).unwrap();
+        let mut rng = StdRng::seed_from_u64(3);
+
+        for _ in 0..1000 {
+            let stops: Vec<usize> = reels.iter().map(|x| x.random_index(&mut rng)).collect();
+            let window = Window::from_columns(
+                reels
+                    .iter()
+                    .zip(&stops)
+                    .map(|(reel, &stop)| reel.window(stop, NUM_ROWS))
+                    .collect(),
+            );
+
+            assert_eq!(
+                table.evaluate_lines(&paytable, &reels, &stops, &paylines, 2),
+                paytable.evaluate_lines(&window, &paylines, 2)
+            );
+        }
+    }
+
+    #[test]
+    fn no_wild_substitution() {
+        let mut paytable = Paytable::default();
+        paytable.set_wild(Some(crate::game::paytable::WildRule { multiplier: 2 }));
+        let reels = vec![ReelStrip::default(); NUM_REELS];
+
+        assert!(LookupTable::new(&paytable, &reels).is_none());
+    }
+
+    #[test]
+    fn only_three_reels() {
+        let reels = vec![ReelStrip::default(); 2];
+
+        assert!(LookupTable::new(&Paytable::default(), &reels).is_none());
+    }
+}
@@ -1,60 +1,83 @@
-use crate::game::payout::payout;
-use crate::game::symbol::Symbol;
+use crate::game::bonus::{FreeSpins, GameMode};
+use crate::game::builder::GameBuilder;
+use crate::game::contribution::Contributions;
+use crate::game::error::{ConfigError, GameError};
+use crate::game::events::{EventListener, GameEvent, Listeners, BIG_WIN};
+use crate::game::fairness::ProvablyFair;
+use crate::game::footprint::MemoryFootprint;
+use crate::game::history::{History, SpinRecord};
+use crate::game::invariant::InvariantMode;
+use crate::game::jackpot::Jackpot;
+use crate::game::ledger::{Ledger, TransactionKind};
+use crate::game::lookup::LookupTable;
+use crate::game::payline::Payline;
+use crate::game::paytable::Paytable;
+use crate::game::reel::ReelStrip;
+use crate::game::rng::GameRng;
+use crate::game::save::SaveFormat;
+use crate::game::shadow::ShadowPlay;
+use crate::game::snapshot::GameSnapshot;
+use crate::game::spin::{LineWin, ScatterWin, SpinResult};
+#[cfg(feature = "async")]
+use crate::game::wallet::AsyncWallet;
+use crate::game::wallet::{MemoryWallet, Wallet};
+use crate::game::window::Window;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use serde_derive::{Deserialize, Serialize};
-use std::error::Error;
-use std::fmt;
+use std::convert::TryFrom;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::SystemTime;
 
+pub mod bonus;
+pub mod builder;
+#[cfg(feature = "client")]
+pub mod client;
+pub mod contribution;
+pub mod diagnostics;
+pub mod error;
+pub mod events;
+pub mod fairness;
+pub mod footprint;
+pub mod history;
+pub mod invariant;
+pub mod jackpot;
+pub mod ledger;
+mod lookup;
+pub mod payline;
 pub mod payout;
+pub mod paytable;
+pub mod reel;
+mod rng;
+pub mod save;
+#[cfg(any(feature = "server", feature = "client"))]
+pub mod server;
+pub mod shadow;
+pub mod simulation;
+pub mod snapshot;
+pub mod spin;
 pub mod symbol;
+pub mod symbol_set;
+#[cfg(feature = "viz")]
+pub mod viz;
+pub mod wallet;
+pub mod window;
 
-/// Number of virtual reels in a slot machine
+/// Default number of reels of a [`Game`], the number of reels is the number of
+/// reel strips, see [`Game::set_reels`]
 pub const NUM_REELS: usize = 3;
 
-#[derive(Debug, Clone)]
-pub struct InvalidBet {
-    bet: u32,
-    bet_min: u32,
-    bet_max: u32,
-}
-
-impl Error for InvalidBet {}
-
-impl fmt::Display for InvalidBet {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let message: &str;
-
-        if self.bet_min > self.bet_max {
-            message = "bet_min > bet_max";
-        } else if self.bet < self.bet_min {
-            message = "bet < bet_min";
-        } else if self.bet > self.bet_max {
-            message = "bet > bet_max";
-        } else {
-            panic!("Unprocessed condition!");
-        }
-
-        write!(f, "{}", message)
-    }
-}
-
-/// This error occurs if there are not enough credits on the balance
-#[derive(Debug, Clone)]
-pub struct LowBalance;
-
-impl Error for LowBalance {}
-
-impl fmt::Display for LowBalance {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Insufficient credits on the balance!")
-    }
-}
+/// Number of visible rows on each reel
+pub const NUM_ROWS: usize = 3;
 
-/// Game state
+/// Game state, the balance is kept in the [`Wallet`] `W`
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub struct Game {
+pub struct Game<W = MemoryWallet> {
     /// Balance
-    credits: u32,
-    /// Actual bet size
+    #[serde(rename = "credits")]
+    wallet: W,
+    /// Actual bet size per line
     bet: u32,
     /// Minimum bet size
     bet_min: u32,
@@ -62,6 +85,49 @@ pub struct Game {
     bet_max: u32,
     /// The amount of the last win
     win: u32,
+    /// Winning combinations
+    paytable: Paytable,
+    /// Reel strips, one per reel
+    reels: Vec<ReelStrip>,
+    /// Active paylines
+    paylines: Vec<Payline>,
+    /// Progressive jackpot, if any
+    #[serde(default)]
+    jackpot: Option<Jackpot>,
+    /// Free spins bonus, if any
+    #[serde(default)]
+    free_spins: Option<FreeSpins>,
+    /// Base game or free spins
+    #[serde(default)]
+    mode: GameMode,
+    /// The most recent spins
+    #[serde(default)]
+    history: History,
+    /// Every change of the balance
+    #[serde(default)]
+    ledger: Ledger,
+    /// Wins of the lifetime of the game per rule and symbol
+    #[serde(default)]
+    contributions: Contributions,
+    /// Candidate configuration evaluated on the same spins, if any
+    #[serde(default)]
+    shadow: Option<ShadowPlay>,
+    /// The balance may change only through the ledger
+    #[serde(default)]
+    strict_money: bool,
+    /// Precomputed payouts of the reels, `None` if the lines are evaluated by the paytable.
+    /// Clones of the game share the table.
+    #[serde(skip)]
+    lookup: Option<Arc<LookupTable>>,
+    /// Source of randomness for spins
+    #[serde(skip)]
+    rng: GameRng,
+    /// What to do when a spin breaks an invariant
+    #[serde(skip)]
+    invariant_mode: InvariantMode,
+    /// Receivers of the events of the game
+    #[serde(skip)]
+    listeners: Listeners,
 }
 
 impl Game {
@@ -69,58 +135,335 @@ impl Game {
     ///
     /// # Examples
     /// ```
+    /// # use slot_machine::game::paytable::Paytable;
     /// # use slot_machine::game::Game;
-    /// Game::new(1000, 1, 1, 100);
+    /// Game::new(1000, 1, 1, 100, Paytable::default());
     /// ```
     ///
     /// # Errors
     ///
-    /// Returns [`InvalidBet`] if bet_min > bet_max or bet < bet_min or bet > bet_max.
-    pub fn new(credits: u32, bet: u32, bet_min: u32, bet_max: u32) -> Result<Self, InvalidBet> {
-        if !Game::validate_bet(bet, bet_min, bet_max) {
-            return Err(InvalidBet {
+    /// Returns [`GameError::InvalidBet`] if bet_min > bet_max or bet < bet_min or bet > bet_max,
+    /// [`GameError::InvalidConfig`] with [`ConfigError::Pays`] if a rule of the `paytable` pays
+    /// too much and [`GameError::Rng`] if the generator cannot be seeded from the operating
+    /// system entropy.
+    pub fn new(
+        credits: u32,
+        bet: u32,
+        bet_min: u32,
+        bet_max: u32,
+        paytable: Paytable,
+    ) -> Result<Self, GameError> {
+        let mut game = Self::without_lookup(credits, bet, bet_min, bet_max, paytable)?;
+        game.index();
+
+        Ok(game)
+    }
+
+    // Same as `new` without the lookup table, the builder indexes the game once it is complete.
+    pub(crate) fn without_lookup(
+        credits: u32,
+        bet: u32,
+        bet_min: u32,
+        bet_max: u32,
+        paytable: Paytable,
+    ) -> Result<Self, GameError> {
+        if !Self::validate_bet(bet, bet_min, bet_max) {
+            return Err(GameError::InvalidBet {
                 bet,
                 bet_min,
                 bet_max,
             });
         }
 
+        paytable.validate()?;
+
+        let reels = vec![ReelStrip::default(); NUM_REELS];
+
         Ok(Game {
-            credits,
+            wallet: MemoryWallet::new(credits),
             bet,
             bet_min,
             bet_max,
             win: 0,
+            lookup: None,
+            paytable,
+            reels,
+            paylines: vec![Payline::default()],
+            jackpot: None,
+            free_spins: None,
+            mode: GameMode::BaseGame,
+            history: History::default(),
+            ledger: Ledger::default(),
+            contributions: Contributions::default(),
+            shadow: None,
+            strict_money: false,
+            rng: GameRng::from_entropy()?,
+            invariant_mode: InvariantMode::default(),
+            listeners: Listeners::default(),
         })
     }
 
+    /// Returns a [`GameBuilder`] to configure a new [`Game`].
+    pub fn builder() -> GameBuilder {
+        GameBuilder::new()
+    }
+
+    /// Converts an instance to a Json object.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GameError::Serialization`] if the game cannot be serialized.
+    pub fn to_json(&self) -> Result<String, GameError> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Restores a game from a Json object created by [`Game::to_json`].
+    ///
+    /// The random number generator of the restored game is seeded from the operating system entropy.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GameError::Serialization`] if `json` is not a game or a reel strip is invalid,
+    /// [`GameError::InvalidBet`] if the restored bet is out of the restored bet limits,
+    /// [`GameError::InvalidConfig`] if the reels, the paytable, the paylines, the free spins
+    /// bonus or the history are invalid and [`GameError::Rng`] if the generator cannot be seeded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use slot_machine::game::paytable::Paytable;
+    /// # use slot_machine::game::Game;
+    /// let mut game = Game::new(1000, 5, 1, 100, Paytable::default()).unwrap();
+    /// game.spin().unwrap();
+    ///
+    /// let restored = Game::from_json(&game.to_json().unwrap()).unwrap();
+    ///
+    /// assert_eq!(restored, game);
+    /// ```
+    pub fn from_json(json: &str) -> Result<Game, GameError> {
+        serde_json::from_str::<Game>(json)?.restore()
+    }
+
+    /// Saves the game to the file at `path`, the file is replaced once the save is complete.
+    ///
+    /// The saved state includes the balance, the bet and the bet limits, the configuration,
+    /// the jackpot pool, the free spins and the history. The random number generator is not saved.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GameError::Io`] if the file cannot be written and
+    /// [`GameError::Serialization`] if the game cannot be serialized.
+    pub fn save_to_file<P: AsRef<Path>>(
+        &self,
+        path: P,
+        format: SaveFormat,
+    ) -> Result<(), GameError> {
+        save::write(self, path.as_ref(), format)
+    }
+
+    /// Loads a game saved by [`Game::save_to_file`] in the same `format`.
+    ///
+    /// The random number generator of the loaded game is seeded from the operating system entropy.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GameError::Io`] if the file cannot be read, [`GameError::Serialization`]
+    /// if the file is not a saved game, [`GameError::InvalidConfig`] if the saved configuration
+    /// is invalid, see [`Game::from_json`], and [`GameError::Rng`] if the generator cannot be
+    /// seeded. A game in strict money mode fails with [`GameError::UnaccountedBalance`] if the saved
+    /// balance differs from its ledger.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use slot_machine::game::paytable::Paytable;
+    /// # use slot_machine::game::save::SaveFormat;
+    /// # use slot_machine::game::Game;
+    /// let game = Game::new(1000, 1, 1, 100, Paytable::default()).unwrap();
+    /// game.save_to_file("game.bin", SaveFormat::Bincode).unwrap();
+    ///
+    /// let loaded = Game::load_from_file("game.bin", SaveFormat::Bincode).unwrap();
+    /// assert_eq!(loaded.credits(), 1000);
+    /// ```
+    pub fn load_from_file<P: AsRef<Path>>(path: P, format: SaveFormat) -> Result<Game, GameError> {
+        save::read::<Game>(path.as_ref(), format)?.restore()
+    }
+
+    // Validates a deserialized game and rebuilds the state that is not serialized.
+    fn restore(mut self) -> Result<Self, GameError> {
+        if !Self::validate_bet(self.bet, self.bet_min, self.bet_max) {
+            return Err(GameError::InvalidBet {
+                bet: self.bet,
+                bet_min: self.bet_min,
+                bet_max: self.bet_max,
+            });
+        }
+
+        let reels = self.reels.len();
+        if reels == 0 {
+            return Err(ConfigError::Reels(reels).into());
+        }
+
+        self.paytable.validate()?;
+
+        // Reel strips are checked when they are deserialized
+        if self.paylines.is_empty() || !self.paylines.iter().all(|x| x.fits(reels, NUM_ROWS)) {
+            return Err(ConfigError::Paylines.into());
+        }
+
+        if let Some(shadow) = &self.shadow {
+            if !shadow.paylines().iter().all(|x| x.fits(reels, NUM_ROWS)) {
+                return Err(ConfigError::Paylines.into());
+            }
+        }
+
+        if let Some(bonus) = &self.free_spins {
+            if bonus.scatters() == 0 || bonus.count() == 0 || bonus.multiplier() == 0 {
+                return Err(ConfigError::FreeSpins.into());
+            }
+        }
+
+        let shape = |x: &Window| x.reels() == reels && x.rows() == NUM_ROWS;
+        if !self.history.iter().all(|x| shape(&x.window)) {
+            return Err(ConfigError::History.into());
+        }
+
+        // A save may come with more transactions than it keeps
+        self.ledger.truncate();
+        self.reconcile(self.wallet.balance())?;
+
+        // Free spins without the bonus or without spins left cannot be played
+        let playable = self.free_spins.is_some();
+        if let GameMode::FreeSpins { remaining } = self.mode {
+            if remaining == 0 || !playable {
+                self.mode = GameMode::BaseGame;
+            }
+        }
+
+        self.index();
+        self.rng = GameRng::from_entropy()?;
+
+        Ok(self)
+    }
+
+    // Returns true if the bet is valid.
+    fn validate_bet(bet: u32, bet_min: u32, bet_max: u32) -> bool {
+        if bet_min > bet_max || bet < bet_min || bet > bet_max {
+            return false;
+        }
+
+        true
+    }
+}
+
+impl<W> Game<W> {
+    /// Moves the balance to the `wallet`, e.g. a wallet backed by a database.
+    ///
+    /// The credits of the game are discarded, every following bet and win
+    /// goes through the `wallet`. See [`Wallet`] for an example.
+    ///
+    /// # Panics
+    ///
+    /// Panics in strict money mode, see [`Game::enable_strict_money`].
+    pub fn with_wallet<V>(self, wallet: V) -> Game<V> {
+        assert!(
+            !self.strict_money,
+            "The wallet cannot be replaced in strict money mode!"
+        );
+
+        Game {
+            wallet,
+            bet: self.bet,
+            bet_min: self.bet_min,
+            bet_max: self.bet_max,
+            win: self.win,
+            paytable: self.paytable,
+            reels: self.reels,
+            paylines: self.paylines,
+            jackpot: self.jackpot,
+            free_spins: self.free_spins,
+            mode: self.mode,
+            history: self.history,
+            ledger: self.ledger,
+            contributions: self.contributions,
+            shadow: self.shadow,
+            strict_money: self.strict_money,
+            lookup: self.lookup,
+            rng: self.rng,
+            invariant_mode: self.invariant_mode,
+            listeners: self.listeners,
+        }
+    }
+
+    /// Returns the wallet keeping the balance
+    pub fn wallet(&self) -> &W {
+        &self.wallet
+    }
+
+    /// Replaces the random number generator used to spin the reels.
+    ///
+    /// By default the generator is seeded from the operating system entropy.
+    /// A generator with a fixed seed makes the sequence of spins reproducible.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rand::rngs::StdRng;
+    /// # use rand::SeedableRng;
+    /// # use slot_machine::game::paytable::Paytable;
+    /// # use slot_machine::game::Game;
+    /// let game = Game::new(1000, 1, 1, 100, Paytable::default()).unwrap();
+    /// let mut a = game.clone().with_rng(StdRng::seed_from_u64(42));
+    /// let mut b = game.with_rng(StdRng::seed_from_u64(42));
+    ///
+    /// assert_eq!(a.spin().unwrap(), b.spin().unwrap());
+    /// ```
+    pub fn with_rng(mut self, rng: StdRng) -> Self {
+        self.rng = GameRng::from(rng);
+
+        self
+    }
+
     /// Bet setter.
     ///
     /// # Errors
     ///
-    /// Returns [`InvalidBet`] if bet < [`Game::bet_min`] or bet > [`Game::bet_max`].
-    pub fn set_bet(&mut self, bet: u32) -> Result<(), InvalidBet> {
-        if !Game::validate_bet(bet, self.bet_min, self.bet_max) {
+    /// Returns [`GameError::InvalidBet`] if bet < [`Game::min`] or bet > [`Game::max`].
+    pub fn set_bet(&mut self, bet: u32) -> Result<(), GameError> {
+        if !<Game>::validate_bet(bet, self.bet_min, self.bet_max) {
             let bet_min = self.bet_min;
             let bet_max = self.bet_max;
 
-            return Err(InvalidBet {
+            return Err(GameError::InvalidBet {
                 bet,
                 bet_min,
                 bet_max,
             });
         }
 
-        self.bet = bet;
+        if bet != self.bet {
+            let previous = self.bet;
+            self.bet = bet;
+            self.listeners.emit(GameEvent::BetChanged {
+                previous,
+                current: bet,
+            });
+        }
 
         Ok(())
     }
 
-    /// Returns the bet size in credits
+    /// Returns the bet size per line in credits
     pub fn bet(&self) -> u32 {
         self.bet
     }
 
+    /// Returns the total bet of a spin, the bet per line multiplied by the number of paylines,
+    /// saturating at `u32::MAX`
+    pub fn total_bet(&self) -> u32 {
+        total_bet(self.bet, self.paylines.len())
+    }
+
     /// Returns the minimum allowable bet
     pub fn min(&self) -> u32 {
         self.bet_min
@@ -132,8 +475,80 @@ impl Game {
     }
 
     /// Returns the number of credits in the balance
-    pub fn credits(&self) -> u32 {
-        self.credits
+    pub fn credits(&self) -> u32
+    where
+        W: Wallet,
+    {
+        self.wallet.balance()
+    }
+
+    /// Adds `amount` credits to the balance and records the deposit in the [`Game::ledger`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GameError::Wallet`] if the [`Wallet`] fails to credit the amount
+    /// and [`GameError::UnaccountedBalance`] in strict money mode if the balance
+    /// changed outside the ledger.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use slot_machine::game::paytable::Paytable;
+    /// # use slot_machine::game::Game;
+    /// let mut game = Game::new(0, 1, 1, 100, Paytable::default()).unwrap();
+    /// game.deposit(500).unwrap();
+    ///
+    /// assert_eq!(game.credits(), 500);
+    /// assert_eq!(game.cash_out().unwrap(), 500);
+    /// assert_eq!(game.credits(), 0);
+    /// ```
+    pub fn deposit(&mut self, amount: u32) -> Result<(), GameError>
+    where
+        W: Wallet,
+    {
+        let previous = self.wallet.balance();
+        self.reconcile(previous)?;
+
+        if amount > 0 {
+            self.wallet.credit(amount)?;
+            self.transaction(
+                TransactionKind::Deposit,
+                amount,
+                previous,
+                self.wallet.balance(),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Withdraws the whole balance and returns the withdrawn amount.
+    ///
+    /// The withdrawal is recorded in the [`Game::ledger`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GameError::Wallet`] if the [`Wallet`] fails to debit the balance
+    /// and [`GameError::UnaccountedBalance`] in strict money mode if the balance
+    /// changed outside the ledger.
+    pub fn cash_out(&mut self) -> Result<u32, GameError>
+    where
+        W: Wallet,
+    {
+        let amount = self.wallet.balance();
+        self.reconcile(amount)?;
+
+        if amount > 0 {
+            self.wallet.debit(amount)?;
+            self.transaction(
+                TransactionKind::CashOut,
+                amount,
+                amount,
+                self.wallet.balance(),
+            );
+        }
+
+        Ok(amount)
     }
 
     /// Returns the amount of the last win
@@ -141,64 +556,743 @@ impl Game {
         self.win
     }
 
+    /// Returns the paytable
+    pub fn paytable(&self) -> &Paytable {
+        &self.paytable
+    }
+
+    /// Returns the reel strips
+    pub fn reels(&self) -> &[ReelStrip] {
+        &self.reels
+    }
+
+    /// Reel strips setter. By default the game has [`NUM_REELS`] reels of [`ReelStrip::default`].
+    ///
+    /// The number of strips is the number of reels, e.g. five strips make a 5-reel video slot.
+    /// If the number of reels changes, the paylines are replaced with [`Payline::horizontal`]
+    /// along the middle row, set the paylines for the new reels with [`Game::set_paylines`].
+    /// The shadow play, whose paylines no longer fit, is removed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `reels` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use slot_machine::game::payline::Payline;
+    /// # use slot_machine::game::paytable::Paytable;
+    /// # use slot_machine::game::reel::ReelStrip;
+    /// # use slot_machine::game::Game;
+    /// let mut game = Game::new(1000, 1, 1, 100, Paytable::default()).unwrap();
+    /// game.set_reels(vec![ReelStrip::default(); 5]);
+    /// game.set_paylines(vec![Payline::horizontal(5, 0), Payline::new(vec![0, 1, 2, 1, 0])]);
+    ///
+    /// assert_eq!(game.spin().unwrap().window.reels(), 5);
+    /// ```
+    pub fn set_reels(&mut self, reels: Vec<ReelStrip>) {
+        assert!(!reels.is_empty(), "`reels` must not be empty!");
+
+        if reels.len() != self.reels.len() {
+            self.paylines = vec![Payline::horizontal(reels.len(), NUM_ROWS / 2)];
+            self.shadow = None;
+        }
+
+        self.reels = reels;
+        self.index();
+    }
+
+    // Builds the lookup table of the reels and the paytable.
+    pub(crate) fn index(&mut self) {
+        self.lookup = LookupTable::new(&self.paytable, &self.reels).map(Arc::new);
+    }
+
+    /// Returns the active paylines
+    pub fn paylines(&self) -> &[Payline] {
+        &self.paylines
+    }
+
+    /// Paylines setter. By default the only payline is [`Payline::default`], the middle row.
+    ///
+    /// The bet is placed on every line, so a spin costs [`Game::total_bet`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `paylines` is empty or a payline does not fit into the reels x [`NUM_ROWS`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use slot_machine::game::payline::Payline;
+    /// # use slot_machine::game::paytable::Paytable;
+    /// # use slot_machine::game::Game;
+    /// let mut game = Game::new(1000, 1, 1, 100, Paytable::default()).unwrap();
+    /// game.set_paylines(vec![
+    ///     Payline::horizontal(3, 0),
+    ///     Payline::horizontal(3, 1),
+    ///     Payline::horizontal(3, 2),
+    ///     Payline::diagonal_down(3),
+    ///     Payline::diagonal_up(3),
+    /// ]);
+    ///
+    /// assert_eq!(game.total_bet(), 5);
+    /// ```
+    pub fn set_paylines(&mut self, paylines: Vec<Payline>) {
+        assert!(!paylines.is_empty(), "`paylines` must not be empty!");
+        assert!(
+            paylines.iter().all(|x| x.fits(self.reels.len(), NUM_ROWS)),
+            "`paylines` must fit into {} reels and {} rows!",
+            self.reels.len(),
+            NUM_ROWS
+        );
+
+        self.paylines = paylines;
+    }
+
+    /// Returns the progressive jackpot, `None` if the game has no jackpot
+    pub fn jackpot(&self) -> Option<&Jackpot> {
+        self.jackpot.as_ref()
+    }
+
+    /// Returns the current size of the progressive jackpot pool, `None` if the game has no jackpot
+    pub fn jackpot_pool(&self) -> Option<u32> {
+        self.jackpot.as_ref().map(|x| x.pool())
+    }
+
+    /// Progressive jackpot setter. By default the game has no jackpot.
+    ///
+    /// With a jackpot every spin contributes to the pool and the first payline of [`Symbol::Jackpot`]
+    /// pays the pool instead of the paytable, see [`Jackpot`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use slot_machine::game::jackpot::Jackpot;
+    /// # use slot_machine::game::paytable::Paytable;
+    /// # use slot_machine::game::Game;
+    /// let mut game = Game::new(1000, 100, 1, 100, Paytable::default()).unwrap();
+    /// game.set_jackpot(Some(Jackpot::new(5000, 2)));
+    /// game.spin().unwrap();
+    ///
+    /// assert!(game.jackpot_pool().unwrap() >= 5002);
+    /// ```
+    ///
+    /// [`Symbol::Jackpot`]: crate::game::symbol::Symbol::Jackpot
+    pub fn set_jackpot(&mut self, jackpot: Option<Jackpot>) {
+        self.jackpot = jackpot;
+    }
+
+    /// Returns the free spins bonus, `None` if the game has no bonus
+    pub fn free_spins(&self) -> Option<&FreeSpins> {
+        self.free_spins.as_ref()
+    }
+
+    /// Free spins bonus setter. By default the game has no bonus.
+    ///
+    /// The bonus is awarded for [`Symbol::Scatter`], so at least one reel must have scatters.
+    /// Removing the bonus ends free spins in progress.
+    ///
+    /// [`Symbol::Scatter`]: crate::game::symbol::Symbol::Scatter
+    pub fn set_free_spins(&mut self, free_spins: Option<FreeSpins>) {
+        if free_spins.is_none() {
+            self.mode = GameMode::BaseGame;
+        }
+
+        self.free_spins = free_spins;
+    }
+
+    /// Returns the current mode, base game or free spins with the number of remaining spins.
+    pub fn mode(&self) -> GameMode {
+        self.mode
+    }
+
+    /// Returns the most recent spins
+    pub fn history(&self) -> &History {
+        &self.history
+    }
+
+    /// Returns the wins of all spins of the game per paytable rule and per symbol
+    pub fn contributions(&self) -> &Contributions {
+        &self.contributions
+    }
+
+    /// Returns the most recent changes of the balance
+    pub fn ledger(&self) -> &Ledger {
+        &self.ledger
+    }
+
+    /// Sets the number of transactions kept in the ledger, [`Ledger::DEFAULT_CAPACITY`] by default.
+    pub fn set_ledger_capacity(&mut self, capacity: usize) {
+        self.ledger.set_capacity(capacity);
+    }
+
+    /// Sets the number of spins kept in the history, [`History::DEFAULT_CAPACITY`] by default.
+    pub fn set_history_capacity(&mut self, capacity: usize) {
+        self.history.set_capacity(capacity);
+    }
+
+    /// Returns the shadow play, `None` if no candidate configuration is evaluated
+    pub fn shadow(&self) -> Option<&ShadowPlay> {
+        self.shadow.as_ref()
+    }
+
+    /// Shadow play setter. By default no candidate configuration is evaluated.
+    ///
+    /// Every following spin is also evaluated with the candidate of the `shadow`,
+    /// without affecting the balance, see [`ShadowPlay`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if a candidate payline does not fit into the reels x [`NUM_ROWS`].
+    pub fn set_shadow(&mut self, shadow: Option<ShadowPlay>) {
+        if let Some(shadow) = &shadow {
+            assert!(
+                shadow
+                    .paylines()
+                    .iter()
+                    .all(|x| x.fits(self.reels.len(), NUM_ROWS)),
+                "`paylines` must fit into {} reels and {} rows!",
+                self.reels.len(),
+                NUM_ROWS
+            );
+        }
+
+        self.shadow = shadow;
+    }
+
+    /// Returns `true` if the balance may change only through the ledger
+    pub fn strict_money(&self) -> bool {
+        self.strict_money
+    }
+
+    /// Allows the balance to change only through spins, deposits and cash outs, all of them
+    /// recorded in the [`Game::ledger`]. The mode cannot be disabled and is saved with the game.
+    ///
+    /// If the ledger does not account for the current balance, e.g. the initial credits,
+    /// the balance is recorded as the opening deposit. From then on every operation and
+    /// [`Game::load_from_file`] fail with [`GameError::UnaccountedBalance`] if the balance
+    /// differs from the last transaction, and the wallet cannot be replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use slot_machine::game::ledger::TransactionKind;
+    /// # use slot_machine::game::paytable::Paytable;
+    /// # use slot_machine::game::Game;
+    /// let mut game = Game::new(1000, 1, 1, 100, Paytable::default()).unwrap();
+    /// game.enable_strict_money();
+    /// game.spin().unwrap();
+    ///
+    /// assert_eq!(game.ledger().total(TransactionKind::Deposit), 1000);
+    /// assert_eq!(game.ledger().last().unwrap().balance, game.credits());
+    /// ```
+    pub fn enable_strict_money(&mut self)
+    where
+        W: Wallet,
+    {
+        if self.strict_money {
+            return;
+        }
+
+        let balance = self.wallet.balance();
+        if self.ledger.last().map_or(0, |x| x.balance) != balance {
+            self.ledger
+                .record(TransactionKind::Deposit, balance, balance);
+        }
+
+        self.strict_money = true;
+    }
+
+    // In strict money mode, returns an error if the `balance` changed outside the ledger.
+    fn reconcile(&self, balance: u32) -> Result<(), GameError> {
+        if !self.strict_money {
+            return Ok(());
+        }
+
+        let ledger = self.ledger.last().map_or(0, |x| x.balance);
+        if balance != ledger {
+            return Err(GameError::UnaccountedBalance { balance, ledger });
+        }
+
+        Ok(())
+    }
+
+    /// Adds the `listener` receiving the events of the game, see [`EventListener`].
+    ///
+    /// Listeners are neither saved nor cloned with the game.
+    pub fn add_listener<L>(&mut self, listener: L)
+    where
+        L: EventListener + Send + Sync + 'static,
+    {
+        self.listeners.push(Box::new(listener));
+    }
+
+    /// Removes all listeners
+    pub fn clear_listeners(&mut self) {
+        self.listeners.clear();
+    }
+
+    // Records the change of the balance from `previous` to `current` in the ledger
+    // and fires `GameEvent::BalanceChanged`.
+    fn transaction(&mut self, kind: TransactionKind, amount: u32, previous: u32, current: u32) {
+        self.ledger.record(kind, amount, current);
+
+        if current != previous {
+            self.listeners
+                .emit(GameEvent::BalanceChanged { previous, current });
+        }
+    }
+
+    /// Sets what happens when a spin breaks one of the game invariants.
+    ///
+    /// By default invariants are checked (and panic) only in debug builds,
+    /// this allows to enable them in release builds, e.g. for staging environments.
+    pub fn set_invariant_mode(&mut self, mode: InvariantMode) {
+        self.invariant_mode = mode;
+    }
+
     /// Simulates the rotation of the reels slot machine.
     ///
-    /// Returns symbols on the reels.
+    /// Returns [`SpinResult`] describing the outcome of the spin.
     /// Also changes state of the [`Game`] depending on the size of the bet and winnings.
     ///
+    /// During free spins the bet is not deducted and the wins are multiplied,
+    /// the game enters and leaves free spins automatically, see [`Game::mode`].
+    ///
     /// # Errors
     ///
-    /// Returns [`LowBalance`] if the number of credits in the balance [`credits`] is less than the bet size [`bet`].
+    /// Returns [`GameError::LowBalance`] if the number of credits in the balance [`credits`] is less than the total bet [`total_bet`]
+    /// and [`GameError::Wallet`] if the [`Wallet`] fails to debit the bet or to credit the win.
+    /// If the win cannot be credited the bet is refunded and the jackpot pool is restored,
+    /// as if the spin never happened. In strict money mode returns [`GameError::UnaccountedBalance`] if the balance changed
+    /// outside the ledger, see [`Game::enable_strict_money`].
     ///
     /// # Examples
     ///
     /// ```
+    /// # use slot_machine::game::paytable::Paytable;
     /// # use slot_machine::game::Game;
-    /// let mut game = Game::new(1000, 1, 1, 100).unwrap();
-    /// game.spin().unwrap();
+    /// let mut game = Game::new(1000, 1, 1, 100, Paytable::default()).unwrap();
+    /// let result = game.spin().unwrap();
     ///
-    /// // The new value of credits equals `old_value_of_credits - bet_size + winning`
-    /// assert_eq!(game.credits(), 1000 - 1 + game.win());
+    /// // The new value of credits equals `old_value_of_credits - total_bet + winning`
+    /// assert_eq!(result.credits, 1000 - 1 + result.win);
+    /// assert_eq!(result.credits, game.credits());
     /// ```
     ///
     /// [`credits`]: #method.credits
-    /// [`bet`]: #method.bet
-    pub fn spin(&mut self) -> Result<Vec<Symbol>, LowBalance> {
-        if self.credits() < self.bet() {
-            return Err(LowBalance);
+    /// [`total_bet`]: #method.total_bet
+    pub fn spin(&mut self) -> Result<SpinResult, GameError>
+    where
+        W: Wallet,
+    {
+        self.play(None)
+    }
+
+    /// Same as [`Game::spin`], but the stops are derived from the provably fair seeds and
+    /// the nonce of `fairness` instead of the random number generator.
+    ///
+    /// The nonce is not used up if the balance is too low for the bet, see [`ProvablyFair`].
+    ///
+    /// # Errors
+    ///
+    /// See [`Game::spin`].
+    pub fn spin_fair(&mut self, fairness: &mut ProvablyFair) -> Result<SpinResult, GameError>
+    where
+        W: Wallet,
+    {
+        self.play(Some(fairness))
+    }
+
+    /// Reproduces the spin number `nonce`, counting from 0, of a game whose generator was
+    /// seeded with `seed`, see [`Game::with_rng`].
+    ///
+    /// The spin is played on a copy, the game is not changed. The outcome depends on the
+    /// state of the game as well, so call it on the game as it was right before the spin,
+    /// e.g. loaded from a save. Spins that failed, e.g. for a low balance, do not count.
+    ///
+    /// # Errors
+    ///
+    /// See [`Game::spin`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rand::rngs::StdRng;
+    /// # use rand::SeedableRng;
+    /// # use slot_machine::game::paytable::Paytable;
+    /// # use slot_machine::game::Game;
+    /// let mut game = Game::new(1000, 1, 1, 100, Paytable::default())
+    ///     .unwrap()
+    ///     .with_rng(StdRng::seed_from_u64(42));
+    /// game.spin().unwrap();
+    ///
+    /// let before = game.clone();
+    /// let disputed = game.spin().unwrap();
+    ///
+    /// assert_eq!(before.replay(42, 1).unwrap(), disputed);
+    /// ```
+    pub fn replay(&self, seed: u64, nonce: u64) -> Result<SpinResult, GameError>
+    where
+        W: Wallet + Clone,
+    {
+        let mut rng = StdRng::seed_from_u64(seed);
+        for _ in 0..nonce {
+            for reel in &self.reels {
+                reel.random_index(&mut rng);
+            }
         }
 
-        let mut stops = Vec::with_capacity(NUM_REELS);
+        let mut game = self.clone().with_rng(rng);
+        game.spin()
+    }
+
+    /// Reproduces the provably fair spin with the revealed `server_seed`, the `client_seed`
+    /// and the `nonce`, see [`Game::spin_fair`].
+    ///
+    /// Like [`Game::replay`] the spin is played on a copy of the game as it is now.
+    ///
+    /// # Errors
+    ///
+    /// See [`Game::spin`].
+    pub fn replay_fair(
+        &self,
+        server_seed: &str,
+        client_seed: &str,
+        nonce: u64,
+    ) -> Result<SpinResult, GameError>
+    where
+        W: Wallet + Clone,
+    {
+        let mut fairness = ProvablyFair::revealed(server_seed, client_seed, nonce);
+
+        self.clone().spin_fair(&mut fairness)
+    }
+
+    // Plays a spin, the stops come from `fairness` if any, otherwise from the generator.
+    fn play(&mut self, fairness: Option<&mut ProvablyFair>) -> Result<SpinResult, GameError>
+    where
+        W: Wallet,
+    {
+        let round = self.begin(self.wallet.balance(), fairness)?;
 
-        for _i in 0..NUM_REELS {
-            stops.push(Symbol::random());
+        if round.bet > 0 {
+            let previous = self.wallet.balance();
+            self.wallet.debit(round.bet)?;
+            self.transaction(
+                TransactionKind::Bet,
+                round.bet,
+                previous,
+                self.wallet.balance(),
+            );
         }
 
-        self.credits -= self.bet();
-        self.win = payout(&stops) * self.bet();
-        self.credits += self.win;
+        let jackpot = self.jackpot.clone();
+        let win = self.win;
+        let (lines, scatter) = self.evaluate(&round);
+
+        if self.win > 0 {
+            let previous = self.wallet.balance();
+            if let Err(e) = self.wallet.credit(self.win) {
+                self.rollback(jackpot, win);
+                if round.bet > 0 {
+                    let previous = self.wallet.balance();
+                    self.wallet.credit(round.bet)?;
+                    self.transaction(
+                        TransactionKind::Refund,
+                        round.bet,
+                        previous,
+                        self.wallet.balance(),
+                    );
+                }
+
+                return Err(e);
+            }
+            self.transaction(
+                TransactionKind::Win,
+                self.win,
+                previous,
+                self.wallet.balance(),
+            );
+        }
 
-        Ok(stops)
+        Ok(self.finish(round, lines, scatter, self.wallet.balance()))
     }
 
-    /// Converts an instance to a Json object.
-    pub fn to_json(&self) -> String {
-        serde_json::to_string(self).unwrap()
+    /// Same as [`Game::spin`] for a wallet of a remote service, the bet and the win
+    /// are awaited. See [`AsyncWallet`] for an example.
+    ///
+    /// # Errors
+    ///
+    /// See [`Game::spin`], the errors of the wallet are returned as is.
+    #[cfg(feature = "async")]
+    pub async fn spin_async(&mut self) -> Result<SpinResult, GameError>
+    where
+        W: AsyncWallet,
+    {
+        let round = self.begin(self.wallet.balance(), None)?;
+
+        if round.bet > 0 {
+            let previous = self.wallet.balance();
+            self.wallet.debit(round.bet).await?;
+            self.transaction(
+                TransactionKind::Bet,
+                round.bet,
+                previous,
+                self.wallet.balance(),
+            );
+        }
+
+        let jackpot = self.jackpot.clone();
+        let win = self.win;
+        let (lines, scatter) = self.evaluate(&round);
+
+        if self.win > 0 {
+            let previous = self.wallet.balance();
+            if let Err(e) = self.wallet.credit(self.win).await {
+                self.rollback(jackpot, win);
+                if round.bet > 0 {
+                    let previous = self.wallet.balance();
+                    self.wallet.credit(round.bet).await?;
+                    self.transaction(
+                        TransactionKind::Refund,
+                        round.bet,
+                        previous,
+                        self.wallet.balance(),
+                    );
+                }
+
+                return Err(e);
+            }
+            self.transaction(
+                TransactionKind::Win,
+                self.win,
+                previous,
+                self.wallet.balance(),
+            );
+        }
+
+        Ok(self.finish(round, lines, scatter, self.wallet.balance()))
     }
 
-    // Returns true if the bet is valid.
-    fn validate_bet(bet: u32, bet_min: u32, bet_max: u32) -> bool {
-        if bet_min > bet_max || bet < bet_min || bet > bet_max {
-            return false;
+    // Checks the `balance` and draws the window, the first step of a spin.
+    fn begin(
+        &mut self,
+        balance: u32,
+        fairness: Option<&mut ProvablyFair>,
+    ) -> Result<Round, GameError> {
+        self.reconcile(balance)?;
+
+        let (bet, multiplier) = match (self.mode, &self.free_spins) {
+            (GameMode::FreeSpins { .. }, Some(bonus)) => (0, bonus.multiplier()),
+            _ => (self.total_bet(), 1),
+        };
+
+        if balance < bet {
+            return Err(GameError::LowBalance);
         }
 
-        true
+        self.listeners.emit(GameEvent::SpinStarted { bet });
+
+        let stops: Vec<usize> = match fairness {
+            Some(fairness) => fairness.next_stops(&self.reels),
+            None => {
+                let rng = &mut self.rng;
+                self.reels.iter().map(|x| x.random_index(rng)).collect()
+            }
+        };
+        let window = Window::from_columns(
+            self.reels
+                .iter()
+                .zip(&stops)
+                .map(|(reel, &stop)| reel.window(stop, NUM_ROWS))
+                .collect(),
+        );
+
+        Ok(Round {
+            bet,
+            multiplier,
+            stops,
+            window,
+            before: self.meters(balance),
+        })
+    }
+
+    // Evaluates the window and sets the win, the step between the bet and the win transactions.
+    fn evaluate(&mut self, round: &Round) -> (Vec<LineWin>, Option<ScatterWin>) {
+        let mut lines = match &self.lookup {
+            Some(lookup) => lookup.evaluate_lines(
+                &self.paytable,
+                &self.reels,
+                &round.stops,
+                &self.paylines,
+                self.bet,
+            ),
+            None => self
+                .paytable
+                .evaluate_lines(&round.window, &self.paylines, self.bet),
+        };
+
+        for line in lines.iter_mut() {
+            line.win = line.win.saturating_mul(round.multiplier);
+        }
+
+        if let Some(jackpot) = &mut self.jackpot {
+            jackpot.contribute(round.bet);
+
+            // The pool is paid once per spin, other jackpot lines keep their paytable win.
+            if let Some(line) = lines.iter_mut().find(|x| jackpot.is_hit(&x.symbols)) {
+                line.win = jackpot.take();
+                line.jackpot = true;
+            }
+        }
+        let scatter = self
+            .paytable
+            .evaluate_scatter(&round.window, self.total_bet())
+            .map(|mut x| {
+                x.win = x.win.saturating_mul(round.multiplier);
+                x
+            });
+
+        self.win = lines
+            .iter()
+            .map(|x| x.win)
+            .chain(scatter.as_ref().map(|x| x.win))
+            .fold(0, u32::saturating_add);
+
+        (lines, scatter)
+    }
+
+    // Undoes the evaluation of a round whose win could not be credited, so the round
+    // never happened: the jackpot pool and the last win are restored.
+    fn rollback(&mut self, jackpot: Option<Jackpot>, win: u32) {
+        self.jackpot = jackpot;
+        self.win = win;
+    }
+
+    // Advances the mode and records the spin, the last step of a spin, `balance` is the
+    // balance after the win.
+    fn finish(
+        &mut self,
+        round: Round,
+        lines: Vec<LineWin>,
+        scatter: Option<ScatterWin>,
+        balance: u32,
+    ) -> SpinResult {
+        self.mode = match &self.free_spins {
+            Some(bonus) => bonus.next_mode(self.mode, &round.window),
+            None => GameMode::BaseGame,
+        };
+
+        let result = SpinResult {
+            window: round.window,
+            bet: round.bet,
+            win: self.win,
+            lines,
+            scatter,
+            multiplier: round.multiplier,
+            mode: self.mode,
+            credits: balance,
+        };
+
+        self.history.push(SpinRecord {
+            timestamp: SystemTime::now(),
+            bet: result.bet,
+            window: result.window.clone(),
+            win: result.win,
+            credits: result.credits,
+        });
+
+        self.contributions.record(&result);
+
+        if let Some(shadow) = &mut self.shadow {
+            shadow.record(&result, self.bet);
+        }
+
+        if self.invariant_mode != InvariantMode::Off {
+            let violations = invariant::check_spin(
+                &round.before,
+                &self.meters(balance),
+                &result,
+                &self.paytable,
+            );
+            invariant::report(self.invariant_mode, violations, &mut self.listeners);
+        }
+
+        for line in result.lines.iter().filter(|x| x.jackpot) {
+            self.listeners
+                .emit(GameEvent::JackpotHit { amount: line.win });
+        }
+
+        let bet = self.total_bet();
+        if self.win > 0 && self.win >= bet.saturating_mul(BIG_WIN) {
+            self.listeners
+                .emit(GameEvent::BigWin { win: self.win, bet });
+        }
+        if self.listeners.is_active() {
+            self.listeners
+                .emit(GameEvent::SpinCompleted(result.clone()));
+        }
+
+        result
+    }
+
+    /// Returns the approximate memory used by the configuration of the game.
+    ///
+    /// Allows to verify that a configuration fits a memory budget before deployment.
+    pub fn memory_footprint(&self) -> MemoryFootprint {
+        MemoryFootprint {
+            reels: footprint::reels(&self.reels),
+            paytable: footprint::paytable(&self.paytable),
+            paylines: footprint::paylines(&self.paylines),
+            lookup: self.lookup.as_ref().map_or(0, |x| x.size()),
+        }
+    }
+
+    /// Returns a [`GameSnapshot`] of the current meters.
+    pub fn snapshot(&self) -> GameSnapshot
+    where
+        W: Wallet,
+    {
+        GameSnapshot::from(self)
+    }
+
+    // Returns the meters with the `credits` as the balance.
+    pub(crate) fn meters(&self, credits: u32) -> GameSnapshot {
+        GameSnapshot {
+            credits,
+            bet: self.bet,
+            bet_min: self.bet_min,
+            bet_max: self.bet_max,
+            win: self.win,
+            mode: self.mode,
+            jackpot_pool: self.jackpot_pool(),
+        }
     }
 }
 
+// Returns the total bet of `lines` lines of `bet` credits, saturating at `u32::MAX`.
+pub(crate) fn total_bet(bet: u32, lines: usize) -> u32 {
+    u32::try_from(lines).map_or(u32::MAX, |x| bet.saturating_mul(x))
+}
+
+// A spin between the draw and the payout.
+struct Round {
+    // Total bet, 0 for a free spin
+    bet: u32,
+    multiplier: u32,
+    stops: Vec<usize>,
+    window: Window,
+    // Meters before the bet
+    before: GameSnapshot,
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::game::error::ErrorCategory;
+    use crate::game::reel::Stop;
+    use crate::game::symbol::Symbol;
 
     #[test]
     fn game_new() {
@@ -209,23 +1303,558 @@ mod test {
 
         assert_eq!(
             Game {
-                credits,
+                wallet: MemoryWallet::new(credits),
                 bet,
                 bet_min,
                 bet_max,
-                win: 0
+                win: 0,
+                paytable: Paytable::default(),
+                reels: vec![ReelStrip::default(); NUM_REELS],
+                paylines: vec![Payline::default()],
+                jackpot: None,
+                free_spins: None,
+                mode: GameMode::BaseGame,
+                history: History::default(),
+                ledger: Ledger::default(),
+                contributions: Contributions::default(),
+                shadow: None,
+                strict_money: false,
+                lookup: LookupTable::new(
+                    &Paytable::default(),
+                    &vec![ReelStrip::default(); NUM_REELS]
+                )
+                .map(Arc::new),
+                rng: GameRng::default(),
+                invariant_mode: InvariantMode::default(),
+                listeners: Listeners::default(),
             },
-            Game::new(credits, bet, bet_min, bet_max).unwrap()
+            Game::new(credits, bet, bet_min, bet_max, Paytable::default()).unwrap()
         )
     }
 
     #[test]
     fn game_spin() {
-        let mut game = Game::new(1000, 1, 1, 10).unwrap();
+        let mut game = Game::new(1000, 1, 1, 10, Paytable::default()).unwrap();
 
         assert!(game.spin().is_ok())
     }
 
+    #[test]
+    fn game_spin_seeded() {
+        let game = Game::new(1000, 1, 1, 10, Paytable::default()).unwrap();
+        let mut a = game.clone().with_rng(StdRng::seed_from_u64(7));
+        let mut b = game.with_rng(StdRng::seed_from_u64(7));
+
+        for _ in 0..100 {
+            assert_eq!(a.spin().unwrap(), b.spin().unwrap());
+        }
+        assert_eq!(a.credits(), b.credits());
+    }
+
+    #[test]
+    fn game_spin_custom_reels() {
+        let mut game = Game::new(1000, 1, 1, 10, Paytable::default()).unwrap();
+        let sevens = ReelStrip::new(vec![Stop {
+            symbol: Symbol::Seven,
+            weight: 1,
+        }]);
+        game.set_reels(vec![sevens; NUM_REELS]);
+
+        let result = game.spin().unwrap();
+
+        assert_eq!(
+            result.window,
+            Window::new(
+                NUM_REELS,
+                NUM_ROWS,
+                vec![Symbol::Seven; NUM_REELS * NUM_ROWS]
+            )
+        );
+        assert_eq!(result.win, 300);
+        assert_eq!(result.lines[0].combination, "3 Seven");
+        assert_eq!(result.credits, 1000 - 1 + 300);
+    }
+
+    #[test]
+    fn game_spin_multiple_paylines() {
+        let mut game = Game::new(1000, 2, 1, 10, Paytable::default()).unwrap();
+        let sevens = ReelStrip::new(vec![Stop {
+            symbol: Symbol::Seven,
+            weight: 1,
+        }]);
+        game.set_reels(vec![sevens; NUM_REELS]);
+        game.set_paylines(vec![
+            Payline::horizontal(NUM_REELS, 0),
+            Payline::diagonal_up(NUM_REELS),
+        ]);
+
+        let result = game.spin().unwrap();
+
+        assert_eq!(result.bet, 4);
+        assert_eq!(result.lines.len(), 2);
+        assert_eq!(result.win, 2 * 300 * 2);
+        assert_eq!(result.credits, 1000 - 4 + 1200);
+    }
+
+    #[test]
+    fn game_spin_ledger() {
+        let mut game = Game::new(1000, 1, 1, 10, Paytable::default()).unwrap();
+        let sevens = ReelStrip::new(vec![Stop {
+            symbol: Symbol::Seven,
+            weight: 1,
+        }]);
+        game.set_reels(vec![sevens; NUM_REELS]);
+
+        game.spin().unwrap();
+        let transactions: Vec<_> = game
+            .ledger()
+            .iter()
+            .map(|x| (x.id, x.kind, x.amount, x.balance))
+            .collect();
+
+        assert_eq!(
+            transactions,
+            vec![
+                (1, TransactionKind::Bet, 1, 999),
+                (2, TransactionKind::Win, 300, 1299)
+            ]
+        );
+    }
+
+    #[test]
+    fn game_deposit_and_cash_out() {
+        let mut game = Game::new(0, 1, 1, 10, Paytable::default()).unwrap();
+        assert!(matches!(game.spin(), Err(GameError::LowBalance)));
+
+        game.deposit(10).unwrap();
+        game.spin().unwrap();
+        let balance = game.credits();
+
+        assert_eq!(game.cash_out().unwrap(), balance);
+        assert_eq!(game.credits(), 0);
+        assert_eq!(game.ledger().total(TransactionKind::Deposit), 10);
+        assert_eq!(
+            game.ledger().total(TransactionKind::CashOut),
+            u64::from(balance)
+        );
+        assert_eq!(game.ledger().last().unwrap().balance, 0);
+        assert_eq!(game.cash_out().unwrap(), 0);
+    }
+
+    #[test]
+    fn game_spin_jackpot() {
+        let mut game = Game::new(1000, 10, 1, 10, Paytable::default()).unwrap();
+        let jackpots = ReelStrip::new(vec![Stop {
+            symbol: Symbol::Jackpot,
+            weight: 1,
+        }]);
+        game.set_reels(vec![jackpots; NUM_REELS]);
+        game.set_jackpot(Some(Jackpot::new(50_000, 10)));
+
+        let result = game.spin().unwrap();
+
+        assert!(result.lines[0].jackpot);
+        assert_eq!(result.win, 50_001);
+        assert_eq!(result.credits, 1000 - 10 + 50_001);
+        assert_eq!(game.jackpot_pool(), Some(50_000));
+    }
+
+    #[test]
+    fn game_spin_credit_fails() {
+        let mut game = Game::new(u32::MAX - 5, 10, 1, 10, Paytable::default()).unwrap();
+        let jackpots = ReelStrip::new(vec![Stop {
+            symbol: Symbol::Jackpot,
+            weight: 1,
+        }]);
+        game.set_reels(vec![jackpots; NUM_REELS]);
+        game.set_jackpot(Some(Jackpot::new(50_000, 10)));
+
+        assert!(matches!(game.spin(), Err(GameError::Wallet { .. })));
+        assert_eq!(game.credits(), u32::MAX - 5);
+        assert_eq!(game.win(), 0);
+        assert_eq!(game.jackpot_pool(), Some(50_000));
+        assert_eq!(game.history().len(), 0);
+        assert_eq!(
+            game.ledger().last().map(|x| (x.kind, x.balance)),
+            Some((TransactionKind::Refund, u32::MAX - 5))
+        );
+    }
+
+    #[test]
+    fn game_spin_jackpot_lines() {
+        let mut game = Game::new(1000, 10, 1, 10, Paytable::default()).unwrap();
+        let jackpots = ReelStrip::new(vec![Stop {
+            symbol: Symbol::Jackpot,
+            weight: 1,
+        }]);
+        game.set_reels(vec![jackpots; NUM_REELS]);
+        game.set_paylines(vec![
+            Payline::horizontal(NUM_REELS, 0),
+            Payline::horizontal(NUM_REELS, 1),
+        ]);
+        game.set_jackpot(Some(Jackpot::new(50_000, 10)));
+
+        let result = game.spin().unwrap();
+        let pays = game.paytable().payout(&[Symbol::Jackpot; NUM_REELS]) * 10;
+
+        assert!(result.lines[0].jackpot);
+        assert!(!result.lines[1].jackpot);
+        assert_eq!(result.lines[0].win, 50_002);
+        assert_eq!(result.lines[1].win, pays);
+        assert_eq!(result.win, 50_002 + pays);
+        assert_eq!(game.jackpot_pool(), Some(50_000));
+    }
+
+    #[test]
+    fn game_spin_saturated_win() {
+        let mut game =
+            Game::new(20_000_000, 20_000_000, 1, 20_000_000, Paytable::default()).unwrap();
+        let sevens = ReelStrip::new(vec![Stop {
+            symbol: Symbol::Seven,
+            weight: 1,
+        }]);
+        game.set_reels(vec![sevens; NUM_REELS]);
+        game.set_invariant_mode(InvariantMode::Panic);
+
+        let result = game.spin().unwrap();
+
+        assert_eq!(result.win, u32::MAX);
+        assert_eq!(result.credits, u32::MAX);
+    }
+
+    #[test]
+    fn game_spin_free_spins() {
+        let mut game = Game::new(1000, 1, 1, 10, Paytable::default()).unwrap();
+        let reel = |symbol| ReelStrip::new(vec![Stop { symbol, weight: 1 }]);
+        game.set_reels(vec![
+            reel(Symbol::Scatter),
+            reel(Symbol::Scatter),
+            reel(Symbol::Cherry),
+        ]);
+        game.set_free_spins(Some(FreeSpins::new(6, 2, 3)));
+
+        let result = game.spin().unwrap();
+        assert_eq!(result.mode, GameMode::FreeSpins { remaining: 2 });
+        assert_eq!(result.credits, 1000 - 1 + 3);
+
+        let result = game.spin().unwrap();
+        assert_eq!(result.bet, 0);
+        assert_eq!(result.win, 3 * 3);
+        assert_eq!(game.mode(), GameMode::FreeSpins { remaining: 3 });
+
+        game.set_reels(vec![reel(Symbol::Blank); NUM_REELS]);
+        game.spin().unwrap();
+        game.spin().unwrap();
+        game.spin().unwrap();
+        assert_eq!(game.mode(), GameMode::BaseGame);
+        assert_eq!(game.credits(), 1000 - 1 + 3 + 9);
+    }
+
+    #[test]
+    #[should_panic]
+    fn game_set_paylines_out_of_window() {
+        let mut game = Game::new(1000, 1, 1, 10, Paytable::default()).unwrap();
+
+        game.set_paylines(vec![Payline::horizontal(NUM_REELS, NUM_ROWS)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn game_set_reels_empty() {
+        let mut game = Game::new(1000, 1, 1, 10, Paytable::default()).unwrap();
+
+        game.set_reels(vec![]);
+    }
+
+    #[test]
+    fn game_spin_five_reels() {
+        let mut game = Game::new(1000, 1, 1, 10, Paytable::default()).unwrap();
+        let sevens = ReelStrip::new(vec![Stop {
+            symbol: Symbol::Seven,
+            weight: 1,
+        }]);
+        game.set_reels(vec![sevens; 5]);
+
+        assert_eq!(game.paylines(), &[Payline::horizontal(5, 1)]);
+
+        let result = game.spin().unwrap();
+
+        assert_eq!(result.window.reels(), 5);
+        assert_eq!(result.lines[0].symbols, vec![Symbol::Seven; 5]);
+        assert_eq!(result.win, 300);
+    }
+
+    #[test]
+    fn error_codes() {
+        let invalid_bet = Game::new(1000, 0, 1, 10, Paytable::default()).unwrap_err();
+        let low_balance = Game::new(0, 1, 1, 10, Paytable::default())
+            .unwrap()
+            .spin()
+            .unwrap_err();
+
+        assert!(matches!(invalid_bet, GameError::InvalidBet { bet: 0, .. }));
+        assert!(matches!(low_balance, GameError::LowBalance));
+        assert_eq!(invalid_bet.code(), 1001);
+        assert_eq!(invalid_bet.category(), ErrorCategory::Validation);
+        assert_eq!(low_balance.code(), 2001);
+        assert_eq!(low_balance.category(), ErrorCategory::Funds);
+    }
+
+    #[test]
+    fn errors_not_retryable() {
+        let invalid_bet = Game::new(1000, 0, 1, 10, Paytable::default()).unwrap_err();
+        let low_balance = Game::new(0, 1, 1, 10, Paytable::default())
+            .unwrap()
+            .spin()
+            .unwrap_err();
+        let overflow = Game::new(0, 1, 1, 10, Paytable::default())
+            .unwrap()
+            .with_wallet(MemoryWallet::new(u32::MAX))
+            .deposit(1)
+            .unwrap_err();
+
+        assert!(!invalid_bet.is_retryable());
+        assert_eq!(invalid_bet.retry_after(), None);
+        assert!(!low_balance.is_retryable());
+        assert_eq!(low_balance.retry_after(), None);
+        assert!(!overflow.is_retryable());
+    }
+
+    #[test]
+    fn game_from_json_inconsistent_bet() {
+        let game = Game::new(1000, 5, 1, 10, Paytable::default()).unwrap();
+        let json = game.to_json().unwrap().replace("\"bet\":5", "\"bet\":50");
+
+        let error = Game::from_json(&json).unwrap_err();
+
+        assert!(matches!(error, GameError::InvalidBet { bet: 50, .. }));
+    }
+
+    #[test]
+    fn game_from_json_stale_free_spins() {
+        let mut game = Game::new(1000, 1, 1, 10, Paytable::default()).unwrap();
+        game.set_free_spins(Some(FreeSpins::new(3, 10, 2)));
+        game.mode = GameMode::FreeSpins { remaining: 0 };
+
+        let restored = Game::from_json(&game.to_json().unwrap()).unwrap();
+        assert_eq!(restored.mode(), GameMode::BaseGame);
+
+        game.mode = GameMode::FreeSpins { remaining: 5 };
+        game.free_spins = None;
+
+        let restored = Game::from_json(&game.to_json().unwrap()).unwrap();
+        assert_eq!(restored.mode(), GameMode::BaseGame);
+    }
+
+    #[test]
+    fn game_from_json_corrupted() {
+        let mut game = Game::new(1000, 1, 1, 10, Paytable::default()).unwrap();
+        game.set_free_spins(Some(FreeSpins::new(3, 10, 2)));
+        game.set_shadow(Some(ShadowPlay::new(
+            Paytable::default(),
+            vec![Payline::default()],
+            100,
+        )));
+        game.spin().unwrap();
+        let json: serde_json::Value = serde_json::from_str(&game.to_json().unwrap()).unwrap();
+        let restore = |pointer: &str, value: serde_json::Value| {
+            let mut json = json.clone();
+            *json.pointer_mut(pointer).unwrap() = value;
+            Game::from_json(&json.to_string())
+        };
+        let config = |result: Result<Game, GameError>| match result {
+            Err(GameError::InvalidConfig(e)) => Some(e),
+            _ => None,
+        };
+
+        assert!(restore("/free_spins/multiplier", 2.into()).is_ok());
+        assert!(matches!(
+            restore("/reels/0/stops", serde_json::json!([])),
+            Err(GameError::Serialization(_))
+        ));
+        assert!(matches!(
+            restore("/reels/0/stops/0/weight", u32::MAX.into()),
+            Err(GameError::Serialization(_))
+        ));
+        assert_eq!(
+            config(restore("/free_spins/multiplier", 0.into())),
+            Some(ConfigError::FreeSpins)
+        );
+        assert_eq!(
+            config(restore(
+                "/shadow/paylines",
+                serde_json::to_value(vec![Payline::horizontal(5, 1)]).unwrap()
+            )),
+            Some(ConfigError::Paylines)
+        );
+        assert_eq!(
+            config(restore("/history/records/0/window/rows", 1.into())),
+            Some(ConfigError::History)
+        );
+    }
+
+    #[test]
+    fn game_events() {
+        let mut game = Game::new(1000, 1, 1, 10, Paytable::default()).unwrap();
+        let jackpots = ReelStrip::new(vec![Stop {
+            symbol: Symbol::Jackpot,
+            weight: 1,
+        }]);
+        game.set_reels(vec![jackpots; NUM_REELS]);
+        game.set_jackpot(Some(Jackpot::new(5000, 10)));
+        let (sender, receiver) = std::sync::mpsc::channel();
+        game.add_listener(sender);
+
+        game.set_bet(2).unwrap();
+        let result = game.spin().unwrap();
+        assert!(!game.clone().listeners.is_active());
+        drop(game);
+
+        let events: Vec<GameEvent> = receiver.iter().collect();
+        assert_eq!(
+            events,
+            vec![
+                GameEvent::BetChanged {
+                    previous: 1,
+                    current: 2
+                },
+                GameEvent::SpinStarted { bet: 2 },
+                GameEvent::BalanceChanged {
+                    previous: 1000,
+                    current: 998
+                },
+                GameEvent::BalanceChanged {
+                    previous: 998,
+                    current: 5998
+                },
+                GameEvent::JackpotHit { amount: 5000 },
+                GameEvent::BigWin { win: 5000, bet: 2 },
+                GameEvent::SpinCompleted(result),
+            ]
+        );
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn game_spin_async() {
+        // A wallet answering after a round trip to the runtime
+        struct YieldingWallet(u32);
+
+        impl AsyncWallet for YieldingWallet {
+            fn balance(&self) -> u32 {
+                self.0
+            }
+
+            async fn debit(&mut self, amount: u32) -> Result<(), GameError> {
+                tokio::task::yield_now().await;
+                self.0 -= amount;
+                Ok(())
+            }
+
+            async fn credit(&mut self, amount: u32) -> Result<(), GameError> {
+                tokio::task::yield_now().await;
+                self.0 += amount;
+                Ok(())
+            }
+        }
+
+        fn assert_send<T: Send>(value: T) -> T {
+            value
+        }
+
+        let sevens = ReelStrip::new(vec![Stop {
+            symbol: Symbol::Seven,
+            weight: 1,
+        }]);
+        let mut game = Game::new(0, 1, 1, 10, Paytable::default())
+            .unwrap()
+            .with_wallet(YieldingWallet(10));
+        game.set_reels(vec![sevens; NUM_REELS]);
+
+        let result = assert_send(game.spin_async()).await.unwrap();
+
+        assert_eq!(result.credits, 10 - 1 + 300);
+        assert_eq!(game.wallet().balance(), result.credits);
+        assert_eq!(game.ledger().len(), 2);
+
+        let mut game = game.with_wallet(YieldingWallet(0));
+        assert!(matches!(
+            game.spin_async().await,
+            Err(GameError::LowBalance)
+        ));
+    }
+
+    #[test]
+    fn game_replay() {
+        let mut game = Game::new(1000, 1, 1, 10, Paytable::default())
+            .unwrap()
+            .with_rng(StdRng::seed_from_u64(7));
+        for _ in 0..5 {
+            game.spin().unwrap();
+        }
+        let before = game.clone();
+        let expected = game.spin().unwrap();
+
+        assert_eq!(before.replay(7, 5).unwrap(), expected);
+        assert_eq!(before.history().len(), 5);
+    }
+
+    #[test]
+    fn game_replay_fair() {
+        let mut game = Game::new(1000, 1, 1, 10, Paytable::default()).unwrap();
+        let mut fairness = ProvablyFair::new("client").unwrap();
+        game.spin_fair(&mut fairness).unwrap();
+        let before = game.clone();
+        let expected = game.spin_fair(&mut fairness).unwrap();
+        let server_seed = fairness.rotate().unwrap();
+
+        assert_eq!(
+            before.replay_fair(&server_seed, "client", 1).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn game_strict_money() {
+        let mut game = Game::new(1000, 1, 1, 10, Paytable::default()).unwrap();
+        game.enable_strict_money();
+        game.spin().unwrap();
+        game.enable_strict_money();
+
+        assert_eq!(game.ledger().total(TransactionKind::Deposit), 1000);
+
+        let balance = game.credits();
+        let json = game.to_json().unwrap().replace(
+            &format!("\"credits\":{}", balance),
+            &format!("\"credits\":{}", balance + 500),
+        );
+        let error = Game::from_json(&json).unwrap_err();
+
+        assert!(matches!(
+            error,
+            GameError::UnaccountedBalance { ledger, .. } if ledger == balance
+        ));
+        assert!(Game::from_json(&game.to_json().unwrap())
+            .unwrap()
+            .strict_money());
+    }
+
+    #[test]
+    #[should_panic]
+    fn game_strict_money_with_wallet() {
+        let mut game = Game::new(1000, 1, 1, 10, Paytable::default()).unwrap();
+        game.enable_strict_money();
+
+        game.with_wallet(MemoryWallet::new(5000));
+    }
+
+    #[test]
+    fn game_from_json_invalid() {
+        let error = Game::from_json("{}").unwrap_err();
+
+        assert!(matches!(error, GameError::Serialization(_)));
+    }
+
     #[test]
     fn game_validate_bet() {
         assert!(Game::validate_bet(1, 1, 10))
@@ -0,0 +1,167 @@
+use crate::game::symbol::Symbol;
+use crate::game::window::{Position, Window};
+use crate::game::{NUM_REELS, NUM_ROWS};
+use serde_derive::{Deserialize, Serialize};
+
+/// A line across the reels, defined by the row it passes on each reel.
+///
+/// # Examples
+///
+/// ```
+/// # use slot_machine::game::payline::Payline;
+/// // V-shape: top row on the outer reels, bottom row on the middle reel
+/// let v = Payline::new(vec![0, 2, 0]);
+///
+/// assert_eq!(v.rows(), &[0, 2, 0]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Payline {
+    rows: Vec<usize>,
+}
+
+impl Payline {
+    /// Creates new [`Payline`], `rows[reel]` is the row of the line on the `reel`.
+    pub fn new(rows: Vec<usize>) -> Self {
+        Payline { rows }
+    }
+
+    /// Creates a straight line along the `row` of `reels` reels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use slot_machine::game::payline::Payline;
+    /// assert_eq!(Payline::horizontal(5, 1).rows(), &[1, 1, 1, 1, 1]);
+    /// ```
+    pub fn horizontal(reels: usize, row: usize) -> Self {
+        Payline::new(vec![row; reels])
+    }
+
+    /// Creates a diagonal line of `reels` reels from the top left to the bottom right corner.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use slot_machine::game::payline::Payline;
+    /// assert_eq!(Payline::diagonal_down(5).rows(), &[0, 0, 1, 1, 2]);
+    /// ```
+    pub fn diagonal_down(reels: usize) -> Self {
+        Payline::new((0..reels).map(|x| diagonal_row(x, reels)).collect())
+    }
+
+    /// Creates a diagonal line of `reels` reels from the bottom left to the top right corner.
+    pub fn diagonal_up(reels: usize) -> Self {
+        Payline::new(
+            (0..reels)
+                .map(|x| (NUM_ROWS - 1) - diagonal_row(x, reels))
+                .collect(),
+        )
+    }
+
+    /// Returns the row of the line on each reel
+    pub fn rows(&self) -> &[usize] {
+        &self.rows
+    }
+
+    /// Returns the position of the line on the `reel`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the line does not cross the `reel`.
+    pub fn position(&self, reel: usize) -> Position {
+        Position {
+            reel,
+            row: self.rows[reel],
+        }
+    }
+
+    /// Returns the symbols on the line.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the line does not fit into the `window`.
+    pub fn symbols(&self, window: &Window) -> Vec<Symbol> {
+        assert_eq!(
+            self.rows.len(),
+            window.reels(),
+            "The line crosses {} reels, the window has {}!",
+            self.rows.len(),
+            window.reels()
+        );
+
+        self.rows
+            .iter()
+            .enumerate()
+            .map(|(reel, &row)| window.at(reel, row))
+            .collect()
+    }
+
+    // Returns true if the line fits into a window of `reels` x `rows`.
+    pub(crate) fn fits(&self, reels: usize, rows: usize) -> bool {
+        self.rows.len() == reels && self.rows.iter().all(|&x| x < rows)
+    }
+}
+
+impl Default for Payline {
+    /// The middle horizontal line.
+    fn default() -> Self {
+        Payline::horizontal(NUM_REELS, NUM_ROWS / 2)
+    }
+}
+
+// Returns the row of a top-down diagonal on the `reel` of `reels` reels.
+fn diagonal_row(reel: usize, reels: usize) -> usize {
+    reel * (NUM_ROWS - 1) / reels.saturating_sub(1).max(1)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::symbol::Symbol::*;
+
+    fn window() -> Window {
+        Window::from_columns(vec![
+            vec![Seven, Bar, Cherry],
+            vec![Blank, Seven, Bar],
+            vec![Jackpot, DoubleBar, Seven],
+        ])
+    }
+
+    #[test]
+    fn horizontal() {
+        assert_eq!(
+            Payline::horizontal(3, 1).symbols(&window()),
+            vec![Bar, Seven, DoubleBar]
+        );
+        assert_eq!(Payline::default(), Payline::horizontal(NUM_REELS, 1));
+    }
+
+    #[test]
+    fn diagonals() {
+        assert_eq!(Payline::diagonal_down(3).rows(), &[0, 1, 2]);
+        assert_eq!(Payline::diagonal_up(3).rows(), &[2, 1, 0]);
+        assert_eq!(Payline::diagonal_down(3).symbols(&window()), vec![Seven; 3]);
+        assert_eq!(Payline::diagonal_up(5).rows(), &[2, 2, 1, 1, 0]);
+        assert_eq!(Payline::diagonal_down(1).rows(), &[0]);
+    }
+
+    #[test]
+    fn horizontal_five_reels() {
+        let window = Window::new(5, 1, vec![Seven; 5]);
+
+        assert_eq!(Payline::horizontal(5, 0).symbols(&window), vec![Seven; 5]);
+    }
+
+    #[test]
+    fn fits() {
+        assert!(Payline::new(vec![0, 2, 0]).fits(3, 3));
+        assert!(!Payline::new(vec![0, 3, 0]).fits(3, 3));
+        assert!(!Payline::new(vec![0, 0]).fits(3, 3));
+    }
+
+    #[test]
+    #[should_panic]
+    fn symbols_window_too_small() {
+        Payline::horizontal(3, 1).symbols(&Window::new(2, 3, vec![Seven; 6]));
+    }
+}
@@ -7,7 +7,7 @@ use crate::game::NUM_REELS;
 /// # Panics
 ///
 /// Panics if the number of elements in the `symbols` is not 3.
-pub fn payout(symbols: &Vec<Symbol>) -> u32 {
+pub fn payout(symbols: &[Symbol]) -> u32 {
     assert_eq!(
         symbols.len(),
         NUM_REELS,
@@ -45,7 +45,7 @@ pub fn payout(symbols: &Vec<Symbol>) -> u32 {
 }
 
 // Returns `true` if `vec` contains the same symbols.
-fn is_all(vec: &Vec<Symbol>, expected: Symbol) -> bool {
+fn is_all(vec: &[Symbol], expected: Symbol) -> bool {
     vec.iter().all(|x| x == &expected)
 }
 
@@ -55,22 +55,22 @@ mod test {
 
     #[test]
     fn test_payout() {
-        assert_eq!(payout(&vec![Jackpot; 3]), 1666);
-        assert_eq!(payout(&vec![Seven; 3]), 300);
-        assert_eq!(payout(&vec![TripleBar; 3]), 100);
-        assert_eq!(payout(&vec![DoubleBar; 3]), 50);
-        assert_eq!(payout(&vec![Bar; 3]), 25);
-        assert_eq!(payout(&vec![Cherry; 3]), 12);
-        assert_eq!(payout(&vec![Bar, DoubleBar, TripleBar]), 12);
-        assert_eq!(payout(&vec![Cherry, Cherry, Blank]), 6);
-        assert_eq!(payout(&vec![Bar, Blank, Cherry]), 3);
-        assert_eq!(payout(&vec![Bar, Blank, Seven]), 0);
+        assert_eq!(payout(&[Jackpot; 3]), 1666);
+        assert_eq!(payout(&[Seven; 3]), 300);
+        assert_eq!(payout(&[TripleBar; 3]), 100);
+        assert_eq!(payout(&[DoubleBar; 3]), 50);
+        assert_eq!(payout(&[Bar; 3]), 25);
+        assert_eq!(payout(&[Cherry; 3]), 12);
+        assert_eq!(payout(&[Bar, DoubleBar, TripleBar]), 12);
+        assert_eq!(payout(&[Cherry, Cherry, Blank]), 6);
+        assert_eq!(payout(&[Bar, Blank, Cherry]), 3);
+        assert_eq!(payout(&[Bar, Blank, Seven]), 0);
     }
 
     #[test]
     #[should_panic]
     fn payout_vec_length_not_3() {
-        payout(&vec![Bar, Blank, Blank, Bar]);
+        payout(&[Bar, Blank, Blank, Bar]);
     }
 
     #[test]
@@ -0,0 +1,970 @@
+use crate::game::error::{ConfigError, GameError};
+use crate::game::payline::Payline;
+use crate::game::spin::{Evaluation, LineWin, ScatterWin};
+use crate::game::symbol::Symbol;
+use crate::game::symbol::Symbol::*;
+use crate::game::total_bet;
+use crate::game::window::Window;
+use serde_derive::{Deserialize, Serialize};
+
+/// The largest payout multiplier of a rule, including the [`WildRule::multiplier`],
+/// and of a scatter rule, see [`Paytable::validate`].
+pub const MAX_PAYS: u32 = 1_000_000;
+
+/// A combination of symbols on the reels.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Combination {
+    /// Every reel shows one of the listed symbols
+    AnyOf(Vec<Symbol>),
+    /// The symbol is shown on exactly `count` reels
+    Count { symbol: Symbol, count: usize },
+    /// Any of the symbols are shown on exactly `count` reels together, e.g. any two of
+    /// Seven and Bar, the symbols may differ
+    CountOf { symbols: Vec<Symbol>, count: usize },
+    /// The symbol is shown on the first `count` reels from the left, e.g. 3 of a kind on 5 reels
+    FromLeft { symbol: Symbol, count: usize },
+}
+
+impl Combination {
+    /// Returns `true` if the `symbols` form the combination.
+    pub fn matches(&self, symbols: &[Symbol]) -> bool {
+        match self {
+            Combination::AnyOf(expected) => symbols.iter().all(|x| expected.contains(x)),
+            Combination::Count { .. } | Combination::CountOf { .. } => {
+                let (expected, count) = self.counted();
+                symbols.iter().filter(|x| expected.contains(x)).count() == count
+            }
+            Combination::FromLeft { symbol, count } => {
+                symbols.len() >= *count && symbols[..*count].iter().all(|x| x == symbol)
+            }
+        }
+    }
+
+    /// Returns `true` if the `symbols` form the combination when every [`Symbol::Wild`]
+    /// may stand for any symbol.
+    pub fn matches_with_wilds(&self, symbols: &[Symbol]) -> bool {
+        match self {
+            Combination::AnyOf(expected) => symbols
+                .iter()
+                .all(|x| *x == Symbol::Wild || expected.contains(x)),
+            Combination::Count { .. } | Combination::CountOf { .. } => {
+                let (expected, count) = self.counted();
+                let exact = symbols.iter().filter(|x| expected.contains(x)).count();
+                let wilds = symbols.iter().filter(|&x| *x == Symbol::Wild).count();
+
+                exact <= count && count <= exact + wilds
+            }
+            Combination::FromLeft { symbol, count } => {
+                symbols.len() >= *count
+                    && symbols[..*count]
+                        .iter()
+                        .all(|x| x == symbol || *x == Symbol::Wild)
+            }
+        }
+    }
+
+    /// Returns the reels whose symbols form the combination, in order.
+    ///
+    /// A [`Symbol::Wild`] is included where it stands for a missing symbol.
+    /// The result is unspecified if the `symbols` do not form the combination.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use slot_machine::game::paytable::Combination;
+    /// # use slot_machine::game::symbol::Symbol::*;
+    /// let two_cherries = Combination::Count { symbol: Cherry, count: 2 };
+    ///
+    /// assert_eq!(two_cherries.reels(&[Cherry, Bar, Cherry]), vec![0, 2]);
+    /// ```
+    pub fn reels(&self, symbols: &[Symbol]) -> Vec<usize> {
+        match self {
+            Combination::AnyOf(_) => (0..symbols.len()).collect(),
+            Combination::Count { .. } | Combination::CountOf { .. } => {
+                let (expected, count) = self.counted();
+                let exact = symbols.iter().filter(|x| expected.contains(x)).count();
+                let mut wilds = count.saturating_sub(exact);
+
+                (0..symbols.len())
+                    .filter(|&reel| {
+                        if expected.contains(&symbols[reel]) {
+                            return true;
+                        }
+                        if symbols[reel] == Symbol::Wild && wilds > 0 {
+                            wilds -= 1;
+                            return true;
+                        }
+                        false
+                    })
+                    .collect()
+            }
+            Combination::FromLeft { count, .. } => (0..*count).collect(),
+        }
+    }
+
+    // Returns the counted symbols and their number of a `Count` or `CountOf`,
+    // nothing is counted for the other combinations.
+    fn counted(&self) -> (&[Symbol], usize) {
+        match self {
+            Combination::Count { symbol, count } => (std::slice::from_ref(symbol), *count),
+            Combination::CountOf { symbols, count } => (symbols, *count),
+            Combination::AnyOf(_) | Combination::FromLeft { .. } => (&[], 0),
+        }
+    }
+}
+
+/// A paytable entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rule {
+    /// Human readable name of the rule, e.g. `3 Seven`
+    pub name: String,
+    /// The combination of symbols
+    pub combination: Combination,
+    /// Payout multiplier, the win is `pays * bet`
+    pub pays: u32,
+}
+
+/// A scatter paytable entry, pays for [`Symbol::Scatter`] anywhere in the window.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScatterRule {
+    /// Human readable name of the rule, e.g. `3 Scatter`
+    pub name: String,
+    /// Minimum number of scatters in the window
+    pub count: usize,
+    /// Payout multiplier, the win is `pays * total bet`
+    pub pays: u32,
+}
+
+/// How the rules matching the same payline pay, see [`Paytable::set_policy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PayPolicy {
+    /// The first matching rule pays, the order of the rules is their priority
+    #[default]
+    First,
+    /// The matching rule paying the most pays, the first one of the rules paying the same
+    Highest,
+    /// Every matching rule pays, the payline wins once per rule
+    All,
+}
+
+/// Substitution of [`Symbol::Wild`] for the symbols of the paytable rules.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WildRule {
+    /// Payout multiplier of a win that needs a wild to match the rule
+    pub multiplier: u32,
+}
+
+/// Table of winning combinations.
+///
+/// Rules are checked in order, by default the first matching rule determines the payout, so
+/// overlapping rules resolve by their order: put `3 Seven` before `Any two of Seven and Bar`.
+/// The [`PayPolicy`] may pay the highest or all matching rules instead.
+/// With a [`WildRule`] a rule also matches when wilds stand for the missing symbols,
+/// such a wild-assisted win pays [`WildRule::multiplier`] times more.
+///
+/// # Examples
+///
+/// ```
+/// # use slot_machine::game::paytable::Paytable;
+/// let paytable = Paytable::from_toml(r#"
+///     [[rules]]
+///     name = "3 Seven"
+///     combination = { any_of = ["Seven"] }
+///     pays = 500
+///
+///     [[rules]]
+///     name = "1 Cherry"
+///     combination = { count = { symbol = "Cherry", count = 1 } }
+///     pays = 2
+/// "#).unwrap();
+///
+/// assert_eq!(paytable.rules().len(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Paytable {
+    rules: Vec<Rule>,
+    #[serde(default)]
+    wild: Option<WildRule>,
+    #[serde(default)]
+    scatters: Vec<ScatterRule>,
+    #[serde(default)]
+    policy: PayPolicy,
+}
+
+impl Paytable {
+    /// Creates new [`Paytable`] from the list of rules, wilds do not substitute
+    /// and the first matching rule pays.
+    pub fn new(rules: Vec<Rule>) -> Self {
+        Paytable {
+            rules,
+            wild: None,
+            scatters: Vec::new(),
+            policy: PayPolicy::First,
+        }
+    }
+
+    /// Deserializes [`Paytable`] from a Json string.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GameError::Serialization`] if `json` is not a valid paytable
+    /// and [`ConfigError::Pays`] if a rule pays more than [`MAX_PAYS`].
+    pub fn from_json(json: &str) -> Result<Self, GameError> {
+        let paytable: Paytable = serde_json::from_str(json)?;
+        paytable.validate()?;
+
+        Ok(paytable)
+    }
+
+    /// Deserializes [`Paytable`] from a TOML string.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GameError::Serialization`] if `toml` is not a valid paytable
+    /// and [`ConfigError::Pays`] if a rule pays more than [`MAX_PAYS`].
+    pub fn from_toml(toml: &str) -> Result<Self, GameError> {
+        let paytable: Paytable = toml::from_str(toml)?;
+        paytable.validate()?;
+
+        Ok(paytable)
+    }
+
+    /// Checks that no rule pays more than [`MAX_PAYS`], with the wild multiplier applied.
+    ///
+    /// A game checks its paytable when it is created or loaded, so the wins of a spin
+    /// cannot overflow for any sane bet.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::Pays`] with the name of the first rule paying too much.
+    /// With [`PayPolicy::All`] a rule pays too much if the line pays too much together with
+    /// the rules before it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use slot_machine::game::paytable::{Paytable, WildRule};
+    /// let mut paytable = Paytable::default();
+    /// assert!(paytable.validate().is_ok());
+    ///
+    /// paytable.set_wild(Some(WildRule { multiplier: 1000 }));
+    /// assert!(paytable.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let multiplier = self.wild.as_ref().map_or(1, |x| x.multiplier);
+        let mut line = Some(0u32);
+        let lines = self.rules.iter().map(|x| {
+            let pays = x.pays.checked_mul(multiplier);
+            if self.policy != PayPolicy::All {
+                return (&x.name, pays);
+            }
+
+            line = line
+                .zip(pays)
+                .and_then(|(line, pays)| line.checked_add(pays));
+            (&x.name, line)
+        });
+        let scatters = self.scatters.iter().map(|x| (&x.name, Some(x.pays)));
+
+        for (name, pays) in lines.chain(scatters) {
+            if pays.map_or(true, |x| x > MAX_PAYS) {
+                return Err(ConfigError::Pays(name.clone()));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the rules in the order they are checked
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Returns how the rules matching the same payline pay
+    pub fn policy(&self) -> PayPolicy {
+        self.policy
+    }
+
+    /// Pay policy setter. By default the first matching rule pays.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use slot_machine::game::paytable::{PayPolicy, Paytable};
+    /// # use slot_machine::game::symbol::Symbol::*;
+    /// let mut paytable = Paytable::default();
+    /// assert_eq!(paytable.payout(&[Bar, Bar, Bar]), 25);
+    ///
+    /// // `3 Bar` and `3 of any Bar`
+    /// paytable.set_policy(PayPolicy::All);
+    /// assert_eq!(paytable.payout(&[Bar, Bar, Bar]), 25 + 12);
+    /// ```
+    pub fn set_policy(&mut self, policy: PayPolicy) {
+        self.policy = policy;
+    }
+
+    /// Returns the wild substitution, `None` if wilds do not substitute
+    pub fn wild(&self) -> Option<&WildRule> {
+        self.wild.as_ref()
+    }
+
+    /// Wild substitution setter. By default wilds do not substitute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use slot_machine::game::paytable::{Paytable, WildRule};
+    /// # use slot_machine::game::symbol::Symbol::*;
+    /// let mut paytable = Paytable::default();
+    /// paytable.set_wild(Some(WildRule { multiplier: 2 }));
+    ///
+    /// assert_eq!(paytable.payout(&[Seven, Wild, Seven]), 2 * 300);
+    /// assert_eq!(paytable.payout(&[Seven, Seven, Seven]), 300);
+    /// ```
+    pub fn set_wild(&mut self, wild: Option<WildRule>) {
+        self.wild = wild;
+    }
+
+    /// Returns the scatter rules in the order they are checked
+    pub fn scatters(&self) -> &[ScatterRule] {
+        &self.scatters
+    }
+
+    /// Scatter rules setter. By default scatters do not pay.
+    ///
+    /// Rules are checked in order, so list them from the highest `count`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use slot_machine::game::paytable::{Paytable, ScatterRule};
+    /// # use slot_machine::game::symbol::Symbol::*;
+    /// # use slot_machine::game::window::Window;
+    /// let mut paytable = Paytable::default();
+    /// paytable.set_scatters(vec![ScatterRule {
+    ///     name: "2 Scatter".to_owned(),
+    ///     count: 2,
+    ///     pays: 5,
+    /// }]);
+    /// let window = Window::from_columns(vec![
+    ///     vec![Scatter, Blank, Blank],
+    ///     vec![Blank, Blank, Blank],
+    ///     vec![Blank, Blank, Scatter],
+    /// ]);
+    ///
+    /// assert_eq!(paytable.evaluate_scatter(&window, 3).unwrap().win, 15);
+    /// ```
+    pub fn set_scatters(&mut self, scatters: Vec<ScatterRule>) {
+        self.scatters = scatters;
+    }
+
+    /// Returns the [`Rule`] paying for the `symbols`, the first one with [`PayPolicy::All`],
+    /// `None` if the `symbols` do not win.
+    pub fn evaluate(&self, symbols: &[Symbol]) -> Option<&Rule> {
+        let mut paying = None;
+        self.for_each_win(symbols, |rule, _| {
+            paying = paying.or(Some(rule));
+        });
+
+        paying
+    }
+
+    /// Calculates payout multiplier for the `symbols`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use slot_machine::game::paytable::Paytable;
+    /// # use slot_machine::game::symbol::Symbol::*;
+    /// let paytable = Paytable::default();
+    ///
+    /// assert_eq!(paytable.payout(&[Seven, Seven, Seven]), 300);
+    /// assert_eq!(paytable.payout(&[Bar, Blank, Seven]), 0);
+    /// ```
+    pub fn payout(&self, symbols: &[Symbol]) -> u32 {
+        let mut payout = 0u32;
+        self.for_each_win(symbols, |_, pays| payout = payout.saturating_add(pays));
+
+        payout
+    }
+
+    /// Evaluates each of the `paylines` over the `window`, returns the winning lines.
+    ///
+    /// `bet` is the bet per line. A win saturates at `u32::MAX`.
+    /// With [`PayPolicy::All`] a payline is listed once per matching rule.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a payline does not fit into the `window`.
+    pub fn evaluate_lines(&self, window: &Window, paylines: &[Payline], bet: u32) -> Vec<LineWin> {
+        let mut lines = Vec::new();
+
+        for (line, payline) in paylines.iter().enumerate() {
+            let symbols = payline.symbols(window);
+
+            self.for_each_win(&symbols, |rule, pays| {
+                lines.push(LineWin {
+                    line,
+                    positions: rule
+                        .combination
+                        .reels(&symbols)
+                        .into_iter()
+                        .map(|reel| payline.position(reel))
+                        .collect(),
+                    combination: rule.name.clone(),
+                    win: pays.saturating_mul(bet),
+                    symbols: symbols.clone(),
+                    jackpot: false,
+                })
+            });
+        }
+
+        lines
+    }
+
+    /// Evaluates the `window` as a spin would for the `bet` per line on the `paylines`,
+    /// without a [`Game`], e.g. for the example boards of a paytable screen.
+    ///
+    /// The scatters pay the total bet, `bet` times the number of `paylines`. Free spins
+    /// multipliers and the progressive jackpot belong to a game and are not applied.
+    /// A win saturates at `u32::MAX`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a payline does not fit into the `window`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use slot_machine::game::payline::Payline;
+    /// # use slot_machine::game::paytable::Paytable;
+    /// # use slot_machine::game::symbol::Symbol::*;
+    /// # use slot_machine::game::window::Window;
+    /// let board = Window::from_columns(vec![vec![Blank, Seven, Blank]; 3]);
+    ///
+    /// let evaluation = Paytable::default().evaluate_window(&board, &[Payline::default()], 5);
+    ///
+    /// assert_eq!(evaluation.win, 300 * 5);
+    /// assert_eq!(evaluation.lines[0].combination, "3 Seven");
+    /// ```
+    ///
+    /// [`Game`]: crate::game::Game
+    pub fn evaluate_window(&self, window: &Window, paylines: &[Payline], bet: u32) -> Evaluation {
+        let lines = self.evaluate_lines(window, paylines, bet);
+        let scatter = self.evaluate_scatter(window, total_bet(bet, paylines.len()));
+        let win = lines
+            .iter()
+            .map(|x| x.win)
+            .chain(scatter.as_ref().map(|x| x.win))
+            .fold(0, u32::saturating_add);
+
+        Evaluation {
+            lines,
+            scatter,
+            win,
+        }
+    }
+
+    /// Evaluates the scatters anywhere in the `window`, returns the win of the first matching
+    /// scatter rule.
+    ///
+    /// `total_bet` is the bet of the whole spin, not per line. A win saturates at `u32::MAX`.
+    pub fn evaluate_scatter(&self, window: &Window, total_bet: u32) -> Option<ScatterWin> {
+        let positions = window.positions_of(Symbol::Scatter);
+        let count = positions.len();
+
+        self.scatters
+            .iter()
+            .find(|rule| count >= rule.count)
+            .map(|rule| ScatterWin {
+                count,
+                positions,
+                combination: rule.name.clone(),
+                win: rule.pays.saturating_mul(total_bet),
+            })
+    }
+
+    // Calls `pay` with every rule paying for the `symbols` under the policy and its payout
+    // multiplier, a rule matched only with the help of wilds pays `WildRule::multiplier` times more.
+    pub(crate) fn for_each_win<'a, F>(&'a self, symbols: &[Symbol], mut pay: F)
+    where
+        F: FnMut(&'a Rule, u32),
+    {
+        let wild = self
+            .wild
+            .as_ref()
+            .filter(|_| symbols.contains(&Symbol::Wild));
+        let mut wins = self.rules.iter().filter_map(|rule| {
+            if rule.combination.matches(symbols) {
+                return Some((rule, rule.pays));
+            }
+
+            wild.filter(|_| rule.combination.matches_with_wilds(symbols))
+                .map(|wild| (rule, rule.pays.saturating_mul(wild.multiplier)))
+        });
+
+        match self.policy {
+            PayPolicy::First => {
+                if let Some((rule, pays)) = wins.next() {
+                    pay(rule, pays);
+                }
+            }
+            PayPolicy::Highest => {
+                // The first of the rules paying the same wins, unlike `Iterator::max_by_key`.
+                let highest = wins.fold(None, |highest, (rule, pays)| match highest {
+                    Some((_, most)) if most >= pays => highest,
+                    _ => Some((rule, pays)),
+                });
+                if let Some((rule, pays)) = highest {
+                    pay(rule, pays);
+                }
+            }
+            PayPolicy::All => wins.for_each(|(rule, pays)| pay(rule, pays)),
+        }
+    }
+}
+
+impl Default for Paytable {
+    /// Paytable of the Bluejay Bonanza Slot Machine.
+    fn default() -> Self {
+        let rule = |name: &str, combination, pays| Rule {
+            name: name.to_owned(),
+            combination,
+            pays,
+        };
+
+        Paytable::new(vec![
+            rule("3 Jackpot", Combination::AnyOf(vec![Jackpot]), 1666),
+            rule("3 Seven", Combination::AnyOf(vec![Seven]), 300),
+            rule("3 TripleBar", Combination::AnyOf(vec![TripleBar]), 100),
+            rule("3 DoubleBar", Combination::AnyOf(vec![DoubleBar]), 50),
+            rule("3 Bar", Combination::AnyOf(vec![Bar]), 25),
+            rule(
+                "3 of any Bar",
+                Combination::AnyOf(vec![Bar, DoubleBar, TripleBar]),
+                12,
+            ),
+            rule("3 Cherry", Combination::AnyOf(vec![Cherry]), 12),
+            rule(
+                "2 Cherry",
+                Combination::Count {
+                    symbol: Cherry,
+                    count: 2,
+                },
+                6,
+            ),
+            rule(
+                "1 Cherry",
+                Combination::Count {
+                    symbol: Cherry,
+                    count: 1,
+                },
+                3,
+            ),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::builder::GameBuilder;
+    use crate::game::invariant::InvariantMode;
+    use crate::game::payout::payout;
+    use crate::game::window::Position;
+    use crate::game::NUM_REELS;
+
+    #[test]
+    fn default_matches_legacy_payout() {
+        let paytable = Paytable::default();
+
+        for a in Symbol::ALL.iter() {
+            for b in Symbol::ALL.iter() {
+                for c in Symbol::ALL.iter() {
+                    let symbols = [*a, *b, *c];
+
+                    assert_eq!(paytable.payout(&symbols), payout(&symbols), "{:?}", symbols);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn validate_pays() {
+        let mut paytable = Paytable::default();
+        paytable.set_wild(Some(WildRule { multiplier: 3 }));
+        assert_eq!(paytable.validate(), Ok(()));
+
+        paytable.set_wild(Some(WildRule {
+            multiplier: u32::MAX,
+        }));
+        assert_eq!(
+            paytable.validate(),
+            Err(ConfigError::Pays("3 Jackpot".to_owned()))
+        );
+        assert_eq!(paytable.payout(&[Jackpot, Wild, Jackpot]), u32::MAX);
+
+        paytable.set_wild(None);
+        paytable.set_scatters(vec![ScatterRule {
+            name: "3 Scatter".to_owned(),
+            count: 3,
+            pays: MAX_PAYS + 1,
+        }]);
+        assert_eq!(
+            paytable.validate(),
+            Err(ConfigError::Pays("3 Scatter".to_owned()))
+        );
+
+        let json = r#"{"rules": [{"name": "Big", "combination": {"any_of": ["Seven"]}, "pays": 4294967295}]}"#;
+        assert!(matches!(
+            Paytable::from_json(json),
+            Err(GameError::InvalidConfig(ConfigError::Pays(_)))
+        ));
+    }
+
+    #[test]
+    fn evaluate_first_matching_rule() {
+        let paytable = Paytable::new(vec![
+            Rule {
+                name: "3 Seven".to_owned(),
+                combination: Combination::AnyOf(vec![Seven]),
+                pays: 100,
+            },
+            Rule {
+                name: "Any Seven".to_owned(),
+                combination: Combination::AnyOf(vec![Seven, Jackpot]),
+                pays: 10,
+            },
+        ]);
+
+        assert_eq!(paytable.evaluate(&[Seven; 3]).unwrap().name, "3 Seven");
+        assert_eq!(paytable.payout(&[Seven, Jackpot, Seven]), 10);
+        assert_eq!(paytable.evaluate(&[Seven, Blank, Seven]), None);
+    }
+
+    #[test]
+    fn pay_policies() {
+        let mut paytable = Paytable::new(vec![
+            Rule {
+                name: "Any Seven".to_owned(),
+                combination: Combination::Count {
+                    symbol: Seven,
+                    count: 1,
+                },
+                pays: 2,
+            },
+            Rule {
+                name: "Seven and Bar".to_owned(),
+                combination: Combination::CountOf {
+                    symbols: vec![Seven, Bar],
+                    count: 2,
+                },
+                pays: 5,
+            },
+            Rule {
+                name: "Bar and Seven".to_owned(),
+                combination: Combination::CountOf {
+                    symbols: vec![Bar, Seven],
+                    count: 2,
+                },
+                pays: 5,
+            },
+        ]);
+        let symbols = [Seven, Bar, Blank];
+        assert_eq!(paytable.payout(&symbols), 2);
+
+        paytable.set_policy(PayPolicy::Highest);
+        assert_eq!(paytable.payout(&symbols), 5);
+        assert_eq!(paytable.evaluate(&symbols).unwrap().name, "Seven and Bar");
+
+        paytable.set_policy(PayPolicy::All);
+        assert_eq!(paytable.payout(&symbols), 12);
+        assert_eq!(paytable.evaluate(&symbols).unwrap().name, "Any Seven");
+        let window = Window::new(3, 1, symbols.to_vec());
+        let lines = paytable.evaluate_lines(&window, &[Payline::horizontal(3, 0)], 1);
+        assert_eq!(lines.len(), 3);
+        assert!(lines.iter().all(|x| x.line == 0));
+
+        let mut game = GameBuilder::new()
+            .credits(1000)
+            .paytable(paytable.clone())
+            .invariant_mode(InvariantMode::Panic)
+            .build()
+            .unwrap();
+        for _ in 0..1000 {
+            game.spin().unwrap();
+        }
+
+        paytable.rules[0].pays = MAX_PAYS - 9;
+        assert_eq!(
+            paytable.validate(),
+            Err(ConfigError::Pays("Bar and Seven".to_owned()))
+        );
+        paytable.set_policy(PayPolicy::First);
+        assert_eq!(paytable.validate(), Ok(()));
+    }
+
+    #[test]
+    fn count_of_mixed_symbols() {
+        let mut paytable = Paytable::new(vec![
+            Rule {
+                name: "3 Seven".to_owned(),
+                combination: Combination::AnyOf(vec![Seven]),
+                pays: 100,
+            },
+            Rule {
+                name: "Any two of Seven and Bar".to_owned(),
+                combination: Combination::CountOf {
+                    symbols: vec![Seven, Bar],
+                    count: 2,
+                },
+                pays: 2,
+            },
+        ]);
+
+        assert_eq!(paytable.payout(&[Seven; 3]), 100);
+        assert_eq!(paytable.payout(&[Seven, Blank, Bar]), 2);
+        assert_eq!(paytable.payout(&[Bar, Seven, Bar]), 0);
+        assert_eq!(paytable.payout(&[Blank, Blank, Bar]), 0);
+        assert_eq!(
+            paytable.rules()[1].combination.reels(&[Seven, Blank, Bar]),
+            vec![0, 2]
+        );
+
+        paytable.set_wild(Some(WildRule { multiplier: 1 }));
+        assert_eq!(paytable.payout(&[Wild, Blank, Bar]), 2);
+        assert_eq!(
+            paytable.rules()[1].combination.reels(&[Wild, Blank, Bar]),
+            vec![0, 2]
+        );
+
+        let toml = r#"
+            [[rules]]
+            name = "Any two of Seven and Bar"
+            combination = { count_of = { symbols = ["Seven", "Bar"], count = 2 } }
+            pays = 2
+        "#;
+        assert_eq!(
+            Paytable::from_toml(toml)
+                .unwrap()
+                .payout(&[Bar, Seven, Cherry]),
+            2
+        );
+    }
+
+    #[test]
+    fn evaluate_lines() {
+        let paytable = Paytable::default();
+        let window = Window::from_columns(vec![
+            vec![Seven, Cherry, Bar],
+            vec![Blank, Seven, Bar],
+            vec![Jackpot, Blank, Seven],
+        ]);
+        let paylines = vec![
+            Payline::horizontal(NUM_REELS, 0),
+            Payline::horizontal(NUM_REELS, 1),
+            Payline::horizontal(NUM_REELS, 2),
+            Payline::diagonal_down(NUM_REELS),
+        ];
+
+        let lines = paytable.evaluate_lines(&window, &paylines, 2);
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].line, 1);
+        assert_eq!(lines[0].combination, "1 Cherry");
+        assert_eq!(lines[0].win, 6);
+        assert_eq!(lines[0].positions, vec![Position { reel: 0, row: 1 }]);
+        assert_eq!(lines[1].line, 3);
+        assert_eq!(lines[1].win, 600);
+        assert_eq!(
+            lines[1].positions,
+            vec![
+                Position { reel: 0, row: 0 },
+                Position { reel: 1, row: 1 },
+                Position { reel: 2, row: 2 },
+            ]
+        );
+    }
+
+    #[test]
+    fn evaluate_window() {
+        let mut paytable = Paytable::default();
+        paytable.set_scatters(vec![ScatterRule {
+            name: "3 Scatter".to_owned(),
+            count: 3,
+            pays: 5,
+        }]);
+        let window = Window::from_columns(vec![vec![Scatter, Seven, Blank]; 3]);
+        let paylines = vec![
+            Payline::horizontal(NUM_REELS, 0),
+            Payline::horizontal(NUM_REELS, 1),
+        ];
+
+        let evaluation = paytable.evaluate_window(&window, &paylines, 2);
+
+        assert_eq!(evaluation.lines.len(), 1);
+        assert_eq!(evaluation.lines[0].win, 600);
+        assert_eq!(evaluation.scatter.as_ref().unwrap().win, 5 * 2 * 2);
+        assert_eq!(evaluation.win, 620);
+    }
+
+    #[test]
+    fn wild_substitution() {
+        let mut paytable = Paytable::default();
+        assert_eq!(paytable.payout(&[Seven, Wild, Seven]), 0);
+
+        paytable.set_wild(Some(WildRule { multiplier: 2 }));
+
+        assert_eq!(paytable.payout(&[Wild, Seven, Wild]), 2 * 300);
+        assert_eq!(paytable.payout(&[Bar, Wild, TripleBar]), 2 * 12);
+        assert_eq!(
+            paytable.evaluate(&[Cherry, Wild, Blank]).unwrap().name,
+            "2 Cherry"
+        );
+        assert_eq!(paytable.payout(&[Wild, Blank, Blank]), 2 * 3);
+        assert_eq!(paytable.payout(&[Wild; 3]), 2 * 1666);
+        // The wild stands for a cherry, the only rule the other symbols allow
+        assert_eq!(paytable.payout(&[Seven, Wild, Bar]), 2 * 3);
+    }
+
+    #[test]
+    fn wild_from_toml() {
+        let paytable = Paytable::from_toml(
+            r#"
+            wild = { multiplier = 3 }
+
+            [[rules]]
+            name = "3 Seven"
+            combination = { any_of = ["Seven"] }
+            pays = 100
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(paytable.payout(&[Seven, Seven, Wild]), 300);
+    }
+
+    #[test]
+    fn combination_reels() {
+        let two = Combination::Count {
+            symbol: Cherry,
+            count: 2,
+        };
+        let three = Combination::FromLeft {
+            symbol: Seven,
+            count: 3,
+        };
+
+        assert_eq!(two.reels(&[Wild, Cherry, Wild]), vec![0, 1]);
+        assert_eq!(
+            three.reels(&[Seven, Wild, Seven, Bar, Seven]),
+            vec![0, 1, 2]
+        );
+        assert_eq!(
+            Combination::AnyOf(vec![Bar]).reels(&[Bar; 3]),
+            vec![0, 1, 2]
+        );
+    }
+
+    #[test]
+    fn scatter_pays_anywhere() {
+        let mut paytable = Paytable::from_toml(
+            r#"
+            rules = []
+
+            [[scatters]]
+            name = "3 Scatter"
+            count = 3
+            pays = 10
+
+            [[scatters]]
+            name = "2 Scatter"
+            count = 2
+            pays = 2
+            "#,
+        )
+        .unwrap();
+        let window = Window::from_columns(vec![
+            vec![Scatter, Blank, Scatter],
+            vec![Blank, Blank, Blank],
+            vec![Scatter, Blank, Blank],
+        ]);
+
+        let scatter = paytable.evaluate_scatter(&window, 5).unwrap();
+
+        assert_eq!(scatter.count, 3);
+        assert_eq!(scatter.combination, "3 Scatter");
+        assert_eq!(scatter.win, 50);
+        assert_eq!(
+            scatter.positions,
+            vec![
+                Position { reel: 0, row: 0 },
+                Position { reel: 2, row: 0 },
+                Position { reel: 0, row: 2 },
+            ]
+        );
+        assert!(paytable
+            .evaluate_scatter(&Window::new(1, 1, vec![Scatter]), 5)
+            .is_none());
+
+        paytable.set_scatters(vec![]);
+        assert!(paytable.evaluate_scatter(&window, 5).is_none());
+    }
+
+    #[test]
+    fn from_left() {
+        let five = Combination::FromLeft {
+            symbol: Seven,
+            count: 5,
+        };
+        let three = Combination::FromLeft {
+            symbol: Seven,
+            count: 3,
+        };
+
+        assert!(five.matches(&[Seven; 5]));
+        assert!(!five.matches(&[Seven; 3]));
+        assert!(three.matches(&[Seven, Seven, Seven, Bar, Blank]));
+        assert!(!three.matches(&[Bar, Seven, Seven, Seven, Blank]));
+        assert!(three.matches_with_wilds(&[Seven, Wild, Seven, Bar, Blank]));
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let paytable = Paytable::default();
+        let json = serde_json::to_string(&paytable).unwrap();
+
+        assert_eq!(Paytable::from_json(&json).unwrap(), paytable);
+    }
+
+    #[test]
+    fn from_toml() {
+        let paytable = Paytable::from_toml(
+            r#"
+            [[rules]]
+            name = "2 Cherry"
+            combination = { count = { symbol = "Cherry", count = 2 } }
+            pays = 8
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(paytable.payout(&[Cherry, Blank, Cherry]), 8);
+    }
+
+    #[test]
+    fn from_toml_invalid_symbol() {
+        let paytable = Paytable::from_toml(
+            r#"
+            [[rules]]
+            name = "3 Lemon"
+            combination = { any_of = ["Lemon"] }
+            pays = 8
+            "#,
+        );
+
+        assert!(paytable.is_err());
+    }
+}
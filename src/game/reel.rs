@@ -0,0 +1,284 @@
+use crate::game::error::ConfigError;
+use crate::game::symbol::Symbol;
+use crate::game::symbol::Symbol::*;
+use rand::distributions::Uniform;
+use rand::Rng;
+use serde_derive::{Deserialize, Serialize};
+use std::convert::TryFrom;
+
+/// A stop on a [`ReelStrip`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Stop {
+    /// The symbol shown when the reel stops here
+    pub symbol: Symbol,
+    /// Relative probability of the reel stopping here
+    pub weight: u32,
+}
+
+/// A physical reel modelled as an ordered list of weighted stops.
+///
+/// A deserialized strip is checked like one created by [`ReelStrip::new`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RawReelStrip")]
+pub struct ReelStrip {
+    stops: Vec<Stop>,
+}
+
+// Unchecked `ReelStrip` as it is serialized.
+#[derive(Deserialize)]
+struct RawReelStrip {
+    stops: Vec<Stop>,
+}
+
+impl TryFrom<RawReelStrip> for ReelStrip {
+    type Error = ConfigError;
+
+    fn try_from(raw: RawReelStrip) -> Result<Self, Self::Error> {
+        let total = raw
+            .stops
+            .iter()
+            .try_fold(0u32, |acc, x| acc.checked_add(x.weight));
+
+        match total {
+            Some(total) if total > 0 => Ok(ReelStrip { stops: raw.stops }),
+            _ => Err(ConfigError::Weights),
+        }
+    }
+}
+
+impl ReelStrip {
+    /// Creates new [`ReelStrip`] from the stops in the order they appear on the reel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stops` is empty, the total weight of the stops is 0 or does not fit into `u32`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use slot_machine::game::reel::{ReelStrip, Stop};
+    /// # use slot_machine::game::symbol::Symbol;
+    /// let reel = ReelStrip::new(vec![
+    ///     Stop { symbol: Symbol::Blank, weight: 3 },
+    ///     Stop { symbol: Symbol::Seven, weight: 1 },
+    /// ]);
+    ///
+    /// assert_eq!(reel.total_weight(), 4);
+    /// ```
+    pub fn new(stops: Vec<Stop>) -> Self {
+        match ReelStrip::try_from(RawReelStrip { stops }) {
+            Ok(reel) => reel,
+            Err(_) => panic!("`stops` must have a positive total weight that fits into u32!"),
+        }
+    }
+
+    /// Returns the stops in the order they appear on the reel
+    pub fn stops(&self) -> &[Stop] {
+        &self.stops
+    }
+
+    /// Returns the number of stops
+    pub fn len(&self) -> usize {
+        self.stops.len()
+    }
+
+    /// Returns `true` if the reel has no stops
+    pub fn is_empty(&self) -> bool {
+        self.stops.is_empty()
+    }
+
+    /// Returns the sum of weights of all stops
+    pub fn total_weight(&self) -> u32 {
+        self.stops.iter().map(|x| x.weight).sum()
+    }
+
+    /// Returns the sum of weights of the stops showing `symbol`
+    pub fn weight_of(&self, symbol: Symbol) -> u32 {
+        self.stops
+            .iter()
+            .filter(|x| x.symbol == symbol)
+            .map(|x| x.weight)
+            .sum()
+    }
+
+    /// Returns the symbol at the stop `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn symbol(&self, index: usize) -> Symbol {
+        self.stops[index].symbol
+    }
+
+    /// Returns `rows` symbols visible when the reel stops at `index`.
+    ///
+    /// The stop is in the middle row, the neighbouring stops are above and below it.
+    /// The strip is circular, so the first stop follows the last one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use slot_machine::game::reel::ReelStrip;
+    /// # use slot_machine::game::symbol::Symbol::*;
+    /// let reel = ReelStrip::default();
+    ///
+    /// assert_eq!(reel.window(0, 3), vec![Blank, Jackpot, Blank]);
+    /// ```
+    pub fn window(&self, index: usize, rows: usize) -> Vec<Symbol> {
+        (0..rows)
+            .map(|row| self.symbol(self.row_index(index, rows, row)))
+            .collect()
+    }
+
+    /// Returns the index of the stop visible in the `row` of `rows` when the reel stops at `index`.
+    ///
+    /// See [`ReelStrip::window`].
+    pub fn row_index(&self, index: usize, rows: usize, row: usize) -> usize {
+        (index + self.len() * rows - rows / 2 + row) % self.len()
+    }
+
+    /// Searches for the stop index corresponding to `number`, each stop occupies `weight` numbers.
+    ///
+    /// Returns `None` if `number` is not less than [`ReelStrip::total_weight`].
+    pub fn index_of(&self, number: u32) -> Option<usize> {
+        let mut upper = 0;
+
+        for (index, stop) in self.stops.iter().enumerate() {
+            upper += stop.weight;
+
+            if number < upper {
+                return Some(index);
+            }
+        }
+
+        None
+    }
+
+    /// Returns a random stop index drawn from `rng`,
+    /// the probability of each stop is proportional to its weight.
+    pub fn random_index<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        let uniform = Uniform::new(0, self.total_weight());
+        let number = rng.sample(uniform);
+
+        self.index_of(number).unwrap()
+    }
+}
+
+impl Default for ReelStrip {
+    /// Physical reel of the Bluejay Bonanza Slot Machine: 22 stops, symbols separated by blanks.
+    ///
+    /// The weights give every symbol the same probability as the virtual reel,
+    /// see [`Symbol::from_number`].
+    fn default() -> Self {
+        let stops = [
+            (Jackpot, 2),
+            (Blank, 7),
+            (Cherry, 5),
+            (Blank, 7),
+            (Bar, 6),
+            (Blank, 7),
+            (Seven, 4),
+            (Blank, 7),
+            (DoubleBar, 7),
+            (Blank, 7),
+            (TripleBar, 6),
+            (Blank, 7),
+            (Bar, 5),
+            (Blank, 6),
+            (DoubleBar, 6),
+            (Blank, 6),
+            (Seven, 4),
+            (Blank, 6),
+            (TripleBar, 5),
+            (Blank, 6),
+            (Bar, 5),
+            (Blank, 7),
+        ];
+
+        ReelStrip::new(
+            stops
+                .iter()
+                .map(|&(symbol, weight)| Stop { symbol, weight })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn default_matches_virtual_reel() {
+        let reel = ReelStrip::default();
+
+        assert_eq!(reel.len(), 22);
+        for symbol in Symbol::ALL.iter() {
+            assert_eq!(reel.weight_of(*symbol), symbol.weight(), "{}", symbol);
+        }
+    }
+
+    #[test]
+    fn index_of_weighted_stops() {
+        let reel = ReelStrip::new(vec![
+            Stop {
+                symbol: Blank,
+                weight: 2,
+            },
+            Stop {
+                symbol: Seven,
+                weight: 0,
+            },
+            Stop {
+                symbol: Cherry,
+                weight: 1,
+            },
+        ]);
+
+        assert_eq!(reel.index_of(0), Some(0));
+        assert_eq!(reel.index_of(1), Some(0));
+        assert_eq!(reel.index_of(2), Some(2));
+        assert_eq!(reel.index_of(3), None);
+    }
+
+    #[test]
+    fn random_index_in_bounds() {
+        let reel = ReelStrip::default();
+
+        for _ in 0..100 {
+            assert!(reel.random_index(&mut rand::thread_rng()) < reel.len());
+        }
+    }
+
+    #[test]
+    fn window() {
+        let reel = ReelStrip::default();
+
+        assert_eq!(reel.window(4, 3), vec![Blank, Bar, Blank]);
+        assert_eq!(reel.window(21, 3), vec![Bar, Blank, Jackpot]);
+        assert_eq!(reel.window(0, 1), vec![Jackpot]);
+    }
+
+    #[test]
+    fn row_index() {
+        let reel = ReelStrip::default();
+
+        assert_eq!(reel.row_index(0, 3, 0), 21);
+        assert_eq!(reel.row_index(0, 3, 2), 1);
+        assert_eq!(reel.row_index(21, 3, 2), 0);
+    }
+
+    #[test]
+    fn weight_of() {
+        let reel = ReelStrip::default();
+
+        assert_eq!(reel.weight_of(Jackpot), 2);
+        assert_eq!(reel.weight_of(Blank), 73);
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_empty() {
+        ReelStrip::new(vec![]);
+    }
+}
@@ -0,0 +1,55 @@
+use rand::rngs::{OsRng, StdRng};
+use rand::{Error, RngCore, SeedableRng};
+
+/// Random number generator of the [`Game`].
+///
+/// The generator is not part of the game state: any two generators are equal,
+/// so games can be compared by their meters.
+///
+/// [`Game`]: crate::game::Game
+#[derive(Debug, Clone)]
+pub(crate) struct GameRng(StdRng);
+
+impl GameRng {
+    // Seeds the generator from the operating system entropy, unlike `Default` reports failures.
+    pub(crate) fn from_entropy() -> Result<Self, Error> {
+        StdRng::from_rng(OsRng).map(GameRng)
+    }
+}
+
+impl From<StdRng> for GameRng {
+    fn from(rng: StdRng) -> Self {
+        GameRng(rng)
+    }
+}
+
+impl Default for GameRng {
+    /// Generator seeded from the operating system entropy.
+    fn default() -> Self {
+        GameRng(StdRng::from_entropy())
+    }
+}
+
+impl PartialEq for GameRng {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.0.try_fill_bytes(dest)
+    }
+}
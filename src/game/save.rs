@@ -0,0 +1,196 @@
+use crate::game::error::GameError;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// File format of a saved [`Game`].
+///
+/// [`Game`]: crate::game::Game
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveFormat {
+    /// Human readable Json
+    Json,
+    /// Compact binary format of [`bincode`]
+    Bincode,
+}
+
+// Writes the `value` to the file at `path` in the `format`.
+// The value is written to a temporary file next to `path` first, which then replaces the file,
+// so a crash while saving leaves the previous save intact.
+pub(crate) fn write<T: Serialize>(
+    value: &T,
+    path: &Path,
+    format: SaveFormat,
+) -> Result<(), GameError> {
+    let temp = temp_path(path);
+    let result = write_synced(value, &temp, format).and_then(|_| Ok(fs::rename(&temp, path)?));
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+
+    result
+}
+
+// Writes the `value` to the file at `path` and waits until it reaches the disk.
+fn write_synced<T: Serialize>(value: &T, path: &Path, format: SaveFormat) -> Result<(), GameError> {
+    let mut writer = BufWriter::new(File::create(path)?);
+
+    match format {
+        SaveFormat::Json => serde_json::to_writer(&mut writer, value)?,
+        SaveFormat::Bincode => bincode::serialize_into(&mut writer, value)?,
+    }
+
+    writer.flush()?;
+    writer.get_ref().sync_all()?;
+
+    Ok(())
+}
+
+// Returns the path of the temporary file of a save to `path`, e.g. `game.json.tmp`.
+fn temp_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().map_or_else(OsString::new, OsString::from);
+    name.push(".tmp");
+
+    path.with_file_name(name)
+}
+
+// Reads a value from the file at `path` in the `format`.
+// A game read this way has neither the caches nor the generator restored.
+pub(crate) fn read<T: DeserializeOwned>(path: &Path, format: SaveFormat) -> Result<T, GameError> {
+    let reader = BufReader::new(File::open(path)?);
+
+    let value = match format {
+        SaveFormat::Json => serde_json::from_reader(reader)?,
+        SaveFormat::Bincode => bincode::deserialize_from(reader)?,
+    };
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::bonus::{FreeSpins, GameMode};
+    use crate::game::builder::GameBuilder;
+    use crate::game::jackpot::Jackpot;
+    use crate::game::payline::Payline;
+    use crate::game::paytable::Paytable;
+    use crate::game::reel::{ReelStrip, Stop};
+    use crate::game::shadow::ShadowPlay;
+    use crate::game::symbol::Symbol;
+    use crate::game::{Game, NUM_REELS};
+    use std::env;
+    use std::fs;
+
+    fn round_trip(format: SaveFormat, name: &str) {
+        let path = env::temp_dir().join(name);
+        let mut game = GameBuilder::new()
+            .credits(1000)
+            .bet(2, 1, 10)
+            .jackpot(Jackpot::new(500, 3))
+            .build()
+            .unwrap();
+        for _ in 0..10 {
+            game.spin().unwrap();
+        }
+
+        game.save_to_file(&path, format).unwrap();
+        let loaded = Game::load_from_file(&path, format).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, game);
+        assert_eq!(loaded.history().len(), 10);
+        assert_eq!(loaded.jackpot_pool(), game.jackpot_pool());
+    }
+
+    // Saves a game in the middle of free spins and checks every feature resumes where it stopped.
+    fn round_trip_mid_feature(format: SaveFormat, name: &str) {
+        let path = env::temp_dir().join(name);
+        let scatters = ReelStrip::new(vec![Stop {
+            symbol: Symbol::Scatter,
+            weight: 1,
+        }]);
+        let mut game = GameBuilder::new()
+            .credits(1000)
+            .reels(vec![scatters; NUM_REELS])
+            .jackpot(Jackpot::new(500, 3))
+            .build()
+            .unwrap();
+        game.set_free_spins(Some(FreeSpins::new(3, 10, 2)));
+        game.set_shadow(Some(ShadowPlay::new(
+            Paytable::default(),
+            vec![Payline::default()],
+            100,
+        )));
+        game.spin().unwrap();
+        game.spin().unwrap();
+
+        game.save_to_file(&path, format).unwrap();
+        let mut loaded = Game::load_from_file(&path, format).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, game);
+        assert_eq!(loaded.mode(), GameMode::FreeSpins { remaining: 19 });
+        assert_eq!(loaded.free_spins(), game.free_spins());
+        assert_eq!(loaded.jackpot_pool(), game.jackpot_pool());
+        assert_eq!(loaded.shadow().unwrap().report().spins, 2);
+
+        let resumed = loaded.spin().unwrap();
+        let expected = game.spin().unwrap();
+
+        assert_eq!(resumed, expected);
+        assert_eq!(resumed.bet, 0);
+        assert_eq!(resumed.multiplier, 2);
+        assert_eq!(loaded.mode(), game.mode());
+        assert_eq!(loaded.credits(), game.credits());
+    }
+
+    #[test]
+    fn json_round_trip() {
+        round_trip(SaveFormat::Json, "slot_machine_save_test.json");
+    }
+
+    #[test]
+    fn bincode_round_trip() {
+        round_trip(SaveFormat::Bincode, "slot_machine_save_test.bin");
+    }
+
+    #[test]
+    fn json_round_trip_mid_feature() {
+        round_trip_mid_feature(SaveFormat::Json, "slot_machine_feature_test.json");
+    }
+
+    #[test]
+    fn bincode_round_trip_mid_feature() {
+        round_trip_mid_feature(SaveFormat::Bincode, "slot_machine_feature_test.bin");
+    }
+
+    #[test]
+    fn failed_save_keeps_previous() {
+        let path = env::temp_dir().join("slot_machine_failed_save_test.json");
+        let game = Game::new(1000, 1, 1, 10, Paytable::default()).unwrap();
+        game.save_to_file(&path, SaveFormat::Json).unwrap();
+
+        // A map with non-string keys cannot be written as Json
+        let mut invalid = std::collections::HashMap::new();
+        invalid.insert((1, 2), 3);
+        assert!(write(&invalid, &path, SaveFormat::Json).is_err());
+
+        let loaded = Game::load_from_file(&path, SaveFormat::Json).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, game);
+        assert!(!temp_path(&path).exists());
+    }
+
+    #[test]
+    fn load_missing_file() {
+        let error = Game::load_from_file("/nonexistent/game.json", SaveFormat::Json).unwrap_err();
+
+        assert!(matches!(error, GameError::Io(_)));
+    }
+}
@@ -0,0 +1,476 @@
+use crate::game::builder::GameBuilder;
+use crate::game::error::{ErrorCategory, GameError};
+use crate::game::paytable::Paytable;
+use crate::game::snapshot::GameSnapshot;
+use crate::game::Game;
+use serde::Serialize;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// Version of the REST API, the routes are served under `/v1` and without a prefix.
+pub const API_VERSION: u32 = 1;
+
+/// The maximum number of sessions of [`Sessions::new`].
+pub const MAX_SESSIONS: usize = 10_000;
+
+/// Sessions of [`Sessions::new`] unused for this long are removed.
+pub const IDLE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Request body of `POST /games`, every field is optional.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NewGame {
+    /// Initial balance, 0 by default
+    pub credits: u32,
+    /// Bet size, 1 by default
+    pub bet: u32,
+    /// Minimum bet size, 1 by default
+    pub bet_min: u32,
+    /// Maximum bet size, 1 by default
+    pub bet_max: u32,
+    /// The paytable, [`Paytable::default`] if missing
+    pub paytable: Option<Paytable>,
+}
+
+impl Default for NewGame {
+    fn default() -> Self {
+        NewGame {
+            credits: 0,
+            bet: 1,
+            bet_min: 1,
+            bet_max: 1,
+            paytable: None,
+        }
+    }
+}
+
+/// Request body of `PUT /games/{id}/bet`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NewBet {
+    /// Bet size
+    pub bet: u32,
+}
+
+/// A session and the meters of its game, the response of `POST /games`, `GET /games/{id}`
+/// and `PUT /games/{id}/bet`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Session {
+    /// Id of the session
+    pub id: u64,
+    /// Meters of the game
+    #[serde(flatten)]
+    pub game: GameSnapshot,
+}
+
+/// Body of an error response.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorBody {
+    /// Stable code of the [`GameError`], missing for errors of the HTTP layer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<u32>,
+    /// Description of the error
+    pub message: String,
+}
+
+/// A Json response to a request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    /// HTTP status code
+    pub status: u16,
+    /// Json body
+    pub body: String,
+}
+
+/// In-memory store of [`Game`] sessions behind a REST API.
+///
+/// The store knows nothing about sockets, [`handle`] maps a request to a response,
+/// so it can be mounted into any HTTP server. The `server` binary serves it with `tiny_http`.
+///
+/// | Request                  | Body                   | Response             |
+/// |--------------------------|------------------------|----------------------|
+/// | `POST /games`            | [`NewGame`]            | `201` [`Session`]    |
+/// | `GET /games/{id}`        |                        | `200` [`Session`]    |
+/// | `POST /games/{id}/spin`  |                        | `200` [`SpinResult`] |
+/// | `PUT /games/{id}/bet`    | [`NewBet`]             | `200` [`Session`]    |
+/// | `DELETE /games/{id}`     |                        | `204`                |
+///
+/// Every route may be prefixed with the [`API_VERSION`], e.g. `POST /v1/games`,
+/// another version is `404`.
+///
+/// Errors are returned as [`ErrorBody`] with the status picked by the [`ErrorCategory`]:
+/// `400` for invalid input and configuration, `402` for a low balance, `409` for a state
+/// conflict and `500` for a storage failure. Unknown sessions and routes are `404`.
+///
+/// A session is removed by `DELETE` or after it was not used for the idle timeout.
+/// Creating a session past the capacity is `503`. Every session has its own lock,
+/// so spins of different sessions run in parallel.
+///
+/// # Examples
+///
+/// ```
+/// # use slot_machine::game::server::Sessions;
+/// let sessions = Sessions::new();
+///
+/// let created = sessions.handle("POST", "/games", r#"{"credits": 100}"#);
+/// assert_eq!(created.status, 201);
+///
+/// let spin = sessions.handle("POST", "/games/1/spin", "");
+/// assert_eq!(spin.status, 200);
+///
+/// let missing = sessions.handle("GET", "/games/2", "");
+/// assert_eq!(missing.status, 404);
+/// ```
+///
+/// [`handle`]: Sessions::handle
+/// [`SpinResult`]: crate::game::spin::SpinResult
+#[derive(Debug)]
+pub struct Sessions {
+    games: Mutex<HashMap<u64, Entry>>,
+    next_id: AtomicU64,
+    capacity: usize,
+    idle_timeout: Duration,
+}
+
+// A game and the time of its last request.
+#[derive(Debug)]
+struct Entry {
+    game: Arc<Mutex<Game>>,
+    used: Instant,
+}
+
+impl Sessions {
+    /// Creates an empty store of at most [`MAX_SESSIONS`] sessions removed after [`IDLE_TIMEOUT`].
+    pub fn new() -> Self {
+        Sessions::with_limits(MAX_SESSIONS, IDLE_TIMEOUT)
+    }
+
+    /// Creates an empty store of at most `capacity` sessions,
+    /// a session is removed once it was not used for `idle_timeout`.
+    pub fn with_limits(capacity: usize, idle_timeout: Duration) -> Self {
+        Sessions {
+            games: Mutex::default(),
+            next_id: AtomicU64::default(),
+            capacity,
+            idle_timeout,
+        }
+    }
+
+    /// Returns the number of sessions
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns true if there are no sessions
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes the sessions not used for the idle timeout and returns how many were removed.
+    ///
+    /// Idle sessions are removed when they are requested or the store is full anyway,
+    /// pruning from time to time frees the memory of the abandoned ones.
+    pub fn prune(&self) -> usize {
+        let mut games = self.lock();
+        let len = games.len();
+        let idle_timeout = self.idle_timeout;
+        games.retain(|_, x| x.used.elapsed() < idle_timeout);
+
+        len - games.len()
+    }
+
+    /// Handles the request with the `method`, the `url` and the `body`, see [`Sessions`].
+    ///
+    /// The query string of the `url` is ignored.
+    pub fn handle(&self, method: &str, url: &str, body: &str) -> Response {
+        let path = url.split('?').next().unwrap_or_default();
+        let mut segments: Vec<&str> = path.split('/').filter(|x| !x.is_empty()).collect();
+
+        if let Some(version) = segments.first().and_then(|x| x.strip_prefix('v')) {
+            if version.parse() == Ok(API_VERSION) {
+                segments.remove(0);
+            } else if version.parse::<u32>().is_ok() {
+                return error(
+                    404,
+                    None,
+                    format!("Unsupported API version, use `/v{}`", API_VERSION),
+                );
+            }
+        }
+
+        match (method, segments.as_slice()) {
+            ("POST", ["games"]) => self.create(body),
+            (_, ["games"]) => method_not_allowed(),
+            (_, ["games", id, ..]) => match id.parse() {
+                Ok(id) => self.route(method, path, id, &segments[2..], body),
+                Err(_) => not_found(path),
+            },
+            _ => not_found(path),
+        }
+    }
+
+    // Routes a request to the session with the `id`.
+    fn route(&self, method: &str, path: &str, id: u64, rest: &[&str], body: &str) -> Response {
+        if !matches!(rest, [] | ["spin"] | ["bet"]) {
+            return not_found(path);
+        }
+
+        if (method, rest) == ("DELETE", &[][..]) {
+            return match self.lock().remove(&id) {
+                Some(_) => Response {
+                    status: 204,
+                    body: String::new(),
+                },
+                None => game_not_found(id),
+            };
+        }
+
+        // The store is locked only to find the game, a spin locks just its session.
+        let game = match self.get(id) {
+            Some(game) => game,
+            None => return game_not_found(id),
+        };
+        let mut game = game.lock().unwrap_or_else(|e| e.into_inner());
+        let game = &mut *game;
+
+        match (method, rest) {
+            ("GET", []) => json(200, &session(id, game)),
+            ("POST", ["spin"]) => match game.spin() {
+                Ok(result) => json(200, &result),
+                Err(e) => game_error(&e),
+            },
+            ("PUT", ["bet"]) => {
+                let request: NewBet = match serde_json::from_str(body) {
+                    Ok(request) => request,
+                    Err(e) => return bad_request(&e),
+                };
+
+                match game.set_bet(request.bet) {
+                    Ok(()) => json(200, &session(id, game)),
+                    Err(e) => game_error(&e),
+                }
+            }
+            _ => method_not_allowed(),
+        }
+    }
+
+    // Creates a session from the `body`, an empty body creates the default game.
+    fn create(&self, body: &str) -> Response {
+        let request: NewGame = if body.trim().is_empty() {
+            NewGame::default()
+        } else {
+            match serde_json::from_str(body) {
+                Ok(request) => request,
+                Err(e) => return bad_request(&e),
+            }
+        };
+
+        let mut builder = GameBuilder::new().credits(request.credits).bet(
+            request.bet,
+            request.bet_min,
+            request.bet_max,
+        );
+        if let Some(paytable) = request.paytable {
+            builder = builder.paytable(paytable);
+        }
+
+        let game = match builder.build() {
+            Ok(game) => game,
+            Err(e) => return game_error(&e),
+        };
+
+        if self.len() >= self.capacity {
+            self.prune();
+        }
+        let mut games = self.lock();
+        if games.len() >= self.capacity {
+            return error(503, None, "Too many games, try again later".to_owned());
+        }
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let response = json(201, &session(id, &game));
+        games.insert(
+            id,
+            Entry {
+                game: Arc::new(Mutex::new(game)),
+                used: Instant::now(),
+            },
+        );
+
+        response
+    }
+
+    // Returns the game with the `id` and marks it used, an idle game is removed instead.
+    fn get(&self, id: u64) -> Option<Arc<Mutex<Game>>> {
+        let mut games = self.lock();
+        let entry = games.get_mut(&id)?;
+
+        if entry.used.elapsed() >= self.idle_timeout {
+            games.remove(&id);
+            return None;
+        }
+        entry.used = Instant::now();
+
+        Some(Arc::clone(&entry.game))
+    }
+
+    // A panic while the store was locked leaves the other sessions usable.
+    fn lock(&self) -> MutexGuard<'_, HashMap<u64, Entry>> {
+        self.games.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for Sessions {
+    fn default() -> Self {
+        Sessions::new()
+    }
+}
+
+// Returns the session with the `id` of the `game`.
+fn session(id: u64, game: &Game) -> Session {
+    Session {
+        id,
+        game: game.snapshot(),
+    }
+}
+
+// Serializes the `value` into a response with the `status`.
+fn json<T: Serialize>(status: u16, value: &T) -> Response {
+    Response {
+        status,
+        body: serde_json::to_string(value).expect("responses are always serializable"),
+    }
+}
+
+// Returns an error response.
+fn error(status: u16, code: Option<u32>, message: String) -> Response {
+    json(status, &ErrorBody { code, message })
+}
+
+// Maps the `e` to a response by its category.
+fn game_error(e: &GameError) -> Response {
+    let status = match e.category() {
+        ErrorCategory::Validation | ErrorCategory::Config => 400,
+        ErrorCategory::Funds => 402,
+        ErrorCategory::State => 409,
+        ErrorCategory::Storage => 500,
+    };
+
+    error(status, Some(e.code()), e.to_string())
+}
+
+// The request body is not valid Json of the expected shape.
+fn bad_request(e: &serde_json::Error) -> Response {
+    error(400, None, format!("Invalid request body: {}", e))
+}
+
+fn game_not_found(id: u64) -> Response {
+    error(404, None, format!("Game {} not found", id))
+}
+
+fn not_found(path: &str) -> Response {
+    error(404, None, format!("No route for `{}`", path))
+}
+
+fn method_not_allowed() -> Response {
+    error(405, None, "Method not allowed".to_owned())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::spin::SpinResult;
+
+    #[test]
+    fn create_spin_and_bet() {
+        let sessions = Sessions::new();
+
+        let created = sessions.handle("POST", "/games", r#"{"credits": 100, "bet_max": 10}"#);
+        let session: Session = serde_json::from_str(&created.body).unwrap();
+        assert_eq!(created.status, 201);
+        assert_eq!(session.id, 1);
+        assert_eq!(session.game.credits, 100);
+
+        let bet = sessions.handle("PUT", "/games/1/bet", r#"{"bet": 5}"#);
+        let session: Session = serde_json::from_str(&bet.body).unwrap();
+        assert_eq!(bet.status, 200);
+        assert_eq!(session.game.bet, 5);
+
+        let spin = sessions.handle("POST", "/games/1/spin", "");
+        let result: SpinResult = serde_json::from_str(&spin.body).unwrap();
+        assert_eq!(spin.status, 200);
+        assert_eq!(result.credits, 100 - 5 + result.win);
+
+        let get = sessions.handle("GET", "/v1/games/1?verbose", "");
+        let session: Session = serde_json::from_str(&get.body).unwrap();
+        assert_eq!(session.game.credits, result.credits);
+        assert_eq!(sessions.len(), 1);
+    }
+
+    #[test]
+    fn errors() {
+        let sessions = Sessions::new();
+        sessions.handle("POST", "/games", "");
+        let code = |response: Response| {
+            let body: ErrorBody = serde_json::from_str(&response.body).unwrap();
+            (response.status, body.code)
+        };
+
+        assert_eq!(
+            code(sessions.handle("POST", "/games/1/spin", "")),
+            (402, Some(2001))
+        );
+        assert_eq!(
+            code(sessions.handle("PUT", "/games/1/bet", r#"{"bet": 2}"#)),
+            (400, Some(1001))
+        );
+        assert_eq!(
+            code(sessions.handle("PUT", "/games/1/bet", "5")),
+            (400, None)
+        );
+        assert_eq!(
+            code(sessions.handle("POST", "/games", r#"{"bet": 5}"#)),
+            (400, Some(1001))
+        );
+        assert_eq!(code(sessions.handle("GET", "/games/2", "")), (404, None));
+        assert_eq!(code(sessions.handle("GET", "/games/x", "")), (404, None));
+        assert_eq!(code(sessions.handle("GET", "/players", "")), (404, None));
+        assert_eq!(code(sessions.handle("GET", "/v2/games/1", "")), (404, None));
+        assert_eq!(
+            code(sessions.handle(
+                "POST",
+                "/games",
+                r#"{"paytable": {"rules": [
+                    {"name": "Big", "combination": {"any_of": ["Seven"]}, "pays": 4294967295}
+                ]}}"#
+            )),
+            (400, Some(5005))
+        );
+        assert_eq!(code(sessions.handle("PATCH", "/games/1", "")), (405, None));
+        assert_eq!(code(sessions.handle("GET", "/games", "")), (405, None));
+        assert_eq!(sessions.len(), 1);
+    }
+
+    #[test]
+    fn delete_and_limits() {
+        let sessions = Sessions::with_limits(1, Duration::from_secs(3600));
+        sessions.handle("POST", "/games", "");
+
+        assert_eq!(sessions.handle("POST", "/games", "").status, 503);
+        assert_eq!(sessions.handle("DELETE", "/games/1", "").status, 204);
+        assert_eq!(sessions.handle("DELETE", "/games/1", "").status, 404);
+        assert_eq!(sessions.handle("POST", "/games", "").status, 201);
+
+        let sessions = Sessions::with_limits(1, Duration::from_secs(0));
+        sessions.handle("POST", "/games", "");
+
+        assert_eq!(sessions.handle("GET", "/games/1", "").status, 404);
+        assert_eq!(sessions.handle("POST", "/games", "").status, 201);
+        assert_eq!(sessions.handle("POST", "/games", "").status, 201);
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions.prune(), 1);
+        assert!(sessions.is_empty());
+    }
+}
@@ -0,0 +1,218 @@
+use crate::game::payline::Payline;
+use crate::game::paytable::Paytable;
+use crate::game::spin::SpinResult;
+use crate::game::total_bet;
+use serde_derive::{Deserialize, Serialize};
+
+/// Statistics of a [`ShadowPlay`], the real game next to the candidate configuration.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct ShadowReport {
+    /// Number of spins evaluated by both configurations
+    pub spins: u64,
+    /// Sum of the bets of the real game
+    pub real_bet: u64,
+    /// Sum of the wins of the real game
+    pub real_win: u64,
+    /// Number of winning spins of the real game
+    pub real_hits: u64,
+    /// Sum of the bets the candidate would take, the same bet per line on its paylines
+    pub shadow_bet: u64,
+    /// Sum of the wins the candidate would pay
+    pub shadow_win: u64,
+    /// Number of spins the candidate would pay
+    pub shadow_hits: u64,
+}
+
+impl ShadowReport {
+    /// Returns the return to player of the real game
+    pub fn real_rtp(&self) -> f64 {
+        ratio(self.real_win, self.real_bet)
+    }
+
+    /// Returns the return to player of the candidate
+    pub fn shadow_rtp(&self) -> f64 {
+        ratio(self.shadow_win, self.shadow_bet)
+    }
+}
+
+/// A candidate paytable and paylines evaluated on every real spin without affecting the player.
+///
+/// The candidate sees exactly the same windows as the player, so the two configurations
+/// are compared on the same draws. Only the first `budget` spins are evaluated, after that
+/// the shadow costs nothing. The candidate does not take part in the progressive jackpot
+/// and the free spins of the game, during free spins it pays with the same multiplier.
+///
+/// # Examples
+///
+/// ```
+/// # use slot_machine::game::payline::Payline;
+/// # use slot_machine::game::paytable::Paytable;
+/// # use slot_machine::game::shadow::ShadowPlay;
+/// # use slot_machine::game::Game;
+/// let mut game = Game::new(1000, 1, 1, 100, Paytable::default()).unwrap();
+/// game.set_shadow(Some(ShadowPlay::new(
+///     Paytable::default(),
+///     vec![Payline::default()],
+///     10_000,
+/// )));
+///
+/// for _ in 0..10 {
+///     game.spin().unwrap();
+/// }
+///
+/// let report = game.shadow().unwrap().report();
+/// assert_eq!(report.spins, 10);
+/// assert_eq!(report.shadow_win, report.real_win);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShadowPlay {
+    paytable: Paytable,
+    paylines: Vec<Payline>,
+    budget: u64,
+    report: ShadowReport,
+}
+
+impl ShadowPlay {
+    /// Creates new [`ShadowPlay`] of the candidate `paytable` and `paylines`
+    /// evaluating up to `budget` spins.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `paylines` is empty.
+    pub fn new(paytable: Paytable, paylines: Vec<Payline>, budget: u64) -> Self {
+        assert!(!paylines.is_empty(), "`paylines` must not be empty!");
+
+        ShadowPlay {
+            paytable,
+            paylines,
+            budget,
+            report: ShadowReport::default(),
+        }
+    }
+
+    /// Returns the candidate paytable
+    pub fn paytable(&self) -> &Paytable {
+        &self.paytable
+    }
+
+    /// Returns the candidate paylines
+    pub fn paylines(&self) -> &[Payline] {
+        &self.paylines
+    }
+
+    /// Returns the maximum number of spins evaluated
+    pub fn budget(&self) -> u64 {
+        self.budget
+    }
+
+    /// Returns `true` if the budget is spent and the spins are no longer evaluated
+    pub fn is_exhausted(&self) -> bool {
+        self.report.spins >= self.budget
+    }
+
+    /// Returns the comparative statistics
+    pub fn report(&self) -> &ShadowReport {
+        &self.report
+    }
+
+    // Evaluates the window of the real spin `result` with the candidate, `bet` is the bet per line.
+    pub(crate) fn record(&mut self, result: &SpinResult, bet: u32) {
+        if self.is_exhausted() {
+            return;
+        }
+
+        // A free spin is free on both sides
+        let total_bet = total_bet(bet, self.paylines.len());
+        let shadow_bet = if result.bet > 0 { total_bet } else { 0 };
+        let lines = self
+            .paytable
+            .evaluate_lines(&result.window, &self.paylines, bet)
+            .iter()
+            .map(|x| x.win)
+            .fold(0, u32::saturating_add);
+        let scatter = self
+            .paytable
+            .evaluate_scatter(&result.window, total_bet)
+            .map_or(0, |x| x.win);
+        let shadow_win = lines
+            .saturating_add(scatter)
+            .saturating_mul(result.multiplier);
+
+        let report = &mut self.report;
+        report.spins += 1;
+        report.real_bet += u64::from(result.bet);
+        report.real_win += u64::from(result.win);
+        report.shadow_bet += u64::from(shadow_bet);
+        report.shadow_win += u64::from(shadow_win);
+        if result.is_win() {
+            report.real_hits += 1;
+        }
+        if shadow_win > 0 {
+            report.shadow_hits += 1;
+        }
+    }
+}
+
+// Returns `numerator / denominator`, 0 if the denominator is 0.
+fn ratio(numerator: u64, denominator: u64) -> f64 {
+    if denominator == 0 {
+        return 0.0;
+    }
+
+    numerator as f64 / denominator as f64
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::bonus::GameMode;
+    use crate::game::paytable::{Combination, Rule};
+    use crate::game::symbol::Symbol;
+    use crate::game::symbol::Symbol::*;
+    use crate::game::window::Window;
+    use crate::game::NUM_REELS;
+
+    fn result(symbols: Vec<Symbol>, bet: u32, win: u32) -> SpinResult {
+        SpinResult {
+            window: Window::from_columns(symbols.into_iter().map(|x| vec![x; 3]).collect()),
+            bet,
+            win,
+            lines: vec![],
+            scatter: None,
+            multiplier: 1,
+            mode: GameMode::BaseGame,
+            credits: 0,
+        }
+    }
+
+    #[test]
+    fn record_candidate() {
+        let candidate = Paytable::new(vec![Rule {
+            name: "3 Seven".to_owned(),
+            combination: Combination::AnyOf(vec![Seven]),
+            pays: 500,
+        }]);
+        let mut shadow = ShadowPlay::new(
+            candidate,
+            vec![
+                Payline::horizontal(NUM_REELS, 0),
+                Payline::horizontal(NUM_REELS, 1),
+            ],
+            2,
+        );
+
+        shadow.record(&result(vec![Seven; 3], 1, 300), 1);
+        shadow.record(&result(vec![Cherry, Blank, Blank], 1, 3), 1);
+        shadow.record(&result(vec![Seven; 3], 1, 300), 1);
+
+        let report = shadow.report();
+        assert!(shadow.is_exhausted());
+        assert_eq!(report.spins, 2);
+        assert_eq!(report.real_win, 303);
+        assert_eq!(report.real_hits, 2);
+        assert_eq!(report.shadow_bet, 4);
+        assert_eq!(report.shadow_win, 1000);
+        assert_eq!(report.shadow_hits, 1);
+        assert_eq!(report.shadow_rtp(), 250.0);
+    }
+}
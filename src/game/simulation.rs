@@ -0,0 +1,745 @@
+use crate::game::builder::GameBuilder;
+use crate::game::contribution::Contributions;
+use crate::game::error::GameError;
+use crate::game::rng::GameRng;
+use crate::game::save::{self, SaveFormat};
+use crate::game::spin::SpinResult;
+use crate::game::symbol::Symbol;
+use crate::game::wallet::MemoryWallet;
+use crate::game::{Game, NUM_ROWS};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::thread;
+
+/// Number of spins between two checkpoints of a [`Simulator`].
+pub const CHECKPOINT_INTERVAL: u64 = 100_000;
+
+/// Statistics of a simulation run by [`simulate`] or a [`Simulator`].
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct SimulationReport {
+    /// Number of spins played
+    pub spins: u64,
+    /// Sum of the bets of all spins
+    pub total_bet: u64,
+    /// Sum of the wins of all spins
+    pub total_win: u64,
+    /// Number of winning spins
+    pub hits: u64,
+    /// The biggest win of a single spin
+    pub max_win: u32,
+    /// Number of spins per amount of the win, including spins without a win
+    pub histogram: BTreeMap<u32, u64>,
+    /// Wins per paytable rule and per symbol
+    #[serde(default)]
+    pub contributions: Contributions,
+}
+
+impl SimulationReport {
+    /// Returns the return to player, the ratio of the total win to the total bet
+    pub fn rtp(&self) -> f64 {
+        if self.total_bet == 0 {
+            return 0.0;
+        }
+
+        self.total_win as f64 / self.total_bet as f64
+    }
+
+    /// Returns the ratio of winning spins to all spins
+    pub fn hit_frequency(&self) -> f64 {
+        if self.spins == 0 {
+            return 0.0;
+        }
+
+        self.hits as f64 / self.spins as f64
+    }
+
+    /// Adds the statistics of the `other` simulation to `self`.
+    ///
+    /// All statistics are integers, so merging is exact and the result does not depend
+    /// on the order of merging.
+    pub fn merge(&mut self, other: &SimulationReport) {
+        self.spins += other.spins;
+        self.total_bet += other.total_bet;
+        self.total_win += other.total_win;
+        self.hits += other.hits;
+        self.max_win = self.max_win.max(other.max_win);
+        for (win, count) in &other.histogram {
+            *self.histogram.entry(*win).or_insert(0) += count;
+        }
+        self.contributions.merge(&other.contributions);
+    }
+
+    // Adds the outcome of a spin to the statistics.
+    fn record(&mut self, result: &SpinResult) {
+        self.spins += 1;
+        self.total_bet += u64::from(result.bet);
+        self.total_win += u64::from(result.win);
+        if result.is_win() {
+            self.hits += 1;
+        }
+        self.max_win = self.max_win.max(result.win);
+        *self.histogram.entry(result.win).or_insert(0) += 1;
+        self.contributions.record(result);
+    }
+}
+
+/// Plays `spins` spins of the game configured by `config` and collects the statistics.
+///
+/// The balance is topped up before every spin, so the credits of the configuration do not matter.
+/// Seed the generator of the `config` to make the report reproducible.
+///
+/// # Errors
+///
+/// Returns [`GameError`] if the `config` is invalid.
+///
+/// # Examples
+///
+/// ```
+/// # use rand::rngs::StdRng;
+/// # use rand::SeedableRng;
+/// # use slot_machine::game::builder::GameBuilder;
+/// # use slot_machine::game::simulation::simulate;
+/// let config = GameBuilder::new().rng(StdRng::seed_from_u64(1));
+/// let report = simulate(&config, 10_000).unwrap();
+///
+/// assert_eq!(report.spins, 10_000);
+/// assert!(report.rtp() > 0.0);
+/// ```
+pub fn simulate(config: &GameBuilder, spins: u64) -> Result<SimulationReport, GameError> {
+    let mut game = config.clone().build()?;
+    let mut report = SimulationReport::default();
+
+    for _ in 0..spins {
+        report.record(&top_up_and_spin(&mut game)?);
+    }
+
+    Ok(report)
+}
+
+/// Plays `spins` spins of the game configured by `config` on `threads` threads.
+///
+/// The spins are split into blocks of [`CHECKPOINT_INTERVAL`] spins. Every block starts
+/// from the configured game with a generator seeded from the `seed` and the block number,
+/// so the report depends only on the `config`, the `spins` and the `seed`:
+/// it is identical for any number of threads and on every platform.
+/// The generator of the `config` is ignored.
+///
+/// # Errors
+///
+/// Returns [`GameError`] if the `config` is invalid.
+///
+/// # Panics
+///
+/// Panics if `threads` is 0.
+///
+/// # Examples
+///
+/// ```
+/// # use slot_machine::game::builder::GameBuilder;
+/// # use slot_machine::game::simulation::simulate_parallel;
+/// let config = GameBuilder::new();
+///
+/// assert_eq!(
+///     simulate_parallel(&config, 300_000, 7, 1).unwrap(),
+///     simulate_parallel(&config, 300_000, 7, 3).unwrap()
+/// );
+/// ```
+pub fn simulate_parallel(
+    config: &GameBuilder,
+    spins: u64,
+    seed: u64,
+    threads: usize,
+) -> Result<SimulationReport, GameError> {
+    assert!(threads > 0, "`threads` must be positive!");
+
+    let game = config.clone().build()?;
+    let blocks = spins.div_ceil(CHECKPOINT_INTERVAL);
+    let threads = threads as u64;
+
+    let results: Vec<Result<Vec<SimulationReport>, GameError>> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|first| {
+                let game = &game;
+                scope.spawn(move || {
+                    (first..blocks)
+                        .step_by(threads as usize)
+                        .map(|block| {
+                            let spins =
+                                CHECKPOINT_INTERVAL.min(spins - block * CHECKPOINT_INTERVAL);
+                            simulate_block(game, seed, block, spins)
+                        })
+                        .collect::<Result<Vec<_>, _>>()
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|x| x.join().expect("simulation thread panicked"))
+            .collect()
+    });
+
+    let mut report = SimulationReport::default();
+    for result in results {
+        for block in result? {
+            report.merge(&block);
+        }
+    }
+
+    Ok(report)
+}
+
+// Plays `spins` spins of the block number `block` of a parallel simulation.
+fn simulate_block(
+    game: &Game,
+    seed: u64,
+    block: u64,
+    spins: u64,
+) -> Result<SimulationReport, GameError> {
+    let mut game = game.clone();
+    game.rng = GameRng::from(block_rng(seed, block));
+    let mut report = SimulationReport::default();
+
+    for _ in 0..spins {
+        report.record(&top_up_and_spin(&mut game)?);
+    }
+
+    Ok(report)
+}
+
+/// A resumable simulation.
+///
+/// Every [`CHECKPOINT_INTERVAL`] spins the simulator takes a [`Checkpoint`] of the statistics
+/// and the game. Save the checkpoint, and an interrupted simulation continues from it
+/// with [`Simulator::resume`] exactly as if it was never interrupted.
+///
+/// # Examples
+///
+/// ```
+/// # use slot_machine::game::builder::GameBuilder;
+/// # use slot_machine::game::simulation::{Simulator, CHECKPOINT_INTERVAL};
+/// let mut simulator = Simulator::new(&GameBuilder::new(), 42).unwrap();
+/// simulator.run(CHECKPOINT_INTERVAL + 10).unwrap();
+/// let checkpoint = simulator.checkpoint().clone();
+///
+/// let mut resumed = Simulator::resume(checkpoint).unwrap();
+/// resumed.run(10).unwrap();
+///
+/// assert_eq!(resumed.report(), simulator.report());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Simulator {
+    seed: u64,
+    game: Game,
+    report: SimulationReport,
+    checkpoint: Checkpoint,
+}
+
+impl Simulator {
+    /// Creates a [`Simulator`] of the game configured by `config`.
+    ///
+    /// The generator of the `config` is ignored, the spins are generated from the `seed`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GameError`] if the `config` is invalid.
+    pub fn new(config: &GameBuilder, seed: u64) -> Result<Self, GameError> {
+        let game = config.clone().build()?;
+        let report = SimulationReport::default();
+
+        Ok(Simulator {
+            checkpoint: Checkpoint::new(seed, &game, &report)?,
+            seed,
+            game,
+            report,
+        })
+    }
+
+    /// Continues the simulation saved in the `checkpoint`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GameError::Serialization`] if the checkpoint is corrupted
+    /// and [`GameError`] if the saved game is invalid.
+    pub fn resume(checkpoint: Checkpoint) -> Result<Self, GameError> {
+        if checkpoint.checksum != checksum(checkpoint.seed, &checkpoint.game, &checkpoint.report)? {
+            return Err(GameError::Serialization(
+                "the checkpoint checksum does not match its content".into(),
+            ));
+        }
+
+        Ok(Simulator {
+            seed: checkpoint.seed,
+            game: checkpoint.game.clone().restore()?,
+            report: checkpoint.report.clone(),
+            checkpoint,
+        })
+    }
+
+    /// Plays `spins` more spins.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GameError`] if a spin fails.
+    pub fn run(&mut self, spins: u64) -> Result<(), GameError> {
+        for _ in 0..spins {
+            // Every block of spins has its own generator, so a block can be replayed from its start
+            if self.report.spins % CHECKPOINT_INTERVAL == 0 {
+                let block = self.report.spins / CHECKPOINT_INTERVAL;
+                self.game.rng = GameRng::from(block_rng(self.seed, block));
+            }
+
+            self.report.record(&top_up_and_spin(&mut self.game)?);
+
+            if self.report.spins % CHECKPOINT_INTERVAL == 0 {
+                self.checkpoint = Checkpoint::new(self.seed, &self.game, &self.report)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the statistics of all spins played so far
+    pub fn report(&self) -> &SimulationReport {
+        &self.report
+    }
+
+    /// Returns the most recent checkpoint
+    pub fn checkpoint(&self) -> &Checkpoint {
+        &self.checkpoint
+    }
+}
+
+// Returns the generator of the block number `block` of a simulation with the `seed`.
+// Both numbers make up the key of the generator, so neighbouring seeds do not share blocks.
+fn block_rng(seed: u64, block: u64) -> StdRng {
+    let mut key = [0; 32];
+    key[..8].copy_from_slice(&seed.to_le_bytes());
+    key[8..16].copy_from_slice(&block.to_le_bytes());
+
+    StdRng::from_seed(key)
+}
+
+/// Saved state of a [`Simulator`], protected by a checksum.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    seed: u64,
+    game: Game,
+    report: SimulationReport,
+    checksum: u64,
+}
+
+impl Checkpoint {
+    /// Returns the seed of the simulation
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the simulated game at the moment of the checkpoint
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// Returns the statistics at the moment of the checkpoint
+    pub fn report(&self) -> &SimulationReport {
+        &self.report
+    }
+
+    /// Saves the checkpoint to the file at `path`, the file is replaced once the save is complete.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GameError::Io`] if the file cannot be written and
+    /// [`GameError::Serialization`] if the checkpoint cannot be serialized.
+    pub fn save_to_file<P: AsRef<Path>>(
+        &self,
+        path: P,
+        format: SaveFormat,
+    ) -> Result<(), GameError> {
+        save::write(self, path.as_ref(), format)
+    }
+
+    /// Loads a checkpoint saved by [`Checkpoint::save_to_file`] in the same `format`.
+    ///
+    /// The checksum is verified by [`Simulator::resume`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GameError::Io`] if the file cannot be read and [`GameError::Serialization`]
+    /// if the file is not a checkpoint.
+    pub fn load_from_file<P: AsRef<Path>>(path: P, format: SaveFormat) -> Result<Self, GameError> {
+        save::read(path.as_ref(), format)
+    }
+
+    // Creates a checkpoint of the simulation state.
+    fn new(seed: u64, game: &Game, report: &SimulationReport) -> Result<Self, GameError> {
+        Ok(Checkpoint {
+            seed,
+            game: game.clone(),
+            report: report.clone(),
+            checksum: checksum(seed, game, report)?,
+        })
+    }
+}
+
+// Returns the 64-bit FNV-1a hash of the serialized simulation state.
+fn checksum(seed: u64, game: &Game, report: &SimulationReport) -> Result<u64, GameError> {
+    let bytes = bincode::serialize(&(seed, game, report))?;
+
+    Ok(bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    }))
+}
+
+// Tops up the balance to the total bet and plays a spin,
+// the balance of a simulation is not real money, so the top-up is not in the ledger.
+pub(crate) fn top_up_and_spin(game: &mut Game) -> Result<SpinResult, GameError> {
+    game.wallet = MemoryWallet::new(game.total_bet());
+    game.strict_money = false;
+
+    game.spin()
+}
+
+/// Exact return to player calculated by [`rtp`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExactRtp {
+    /// Expected ratio of the win to the bet
+    pub rtp: f64,
+    /// Probability that a spin wins on at least one payline or with the scatters
+    pub hit_frequency: f64,
+    /// Share of the `rtp` per name of the line or scatter rule, rules that never win are missing
+    pub contributions: BTreeMap<String, f64>,
+}
+
+/// Calculates the exact return to player of the game configured by `config`.
+///
+/// Enumerates every combination of reel stops weighted by the stop weights and evaluates
+/// the paylines and the scatters with the paytable. The progressive jackpot and free spins
+/// are not included, a jackpot line counts with its paytable payout.
+/// The time grows with the product of the reel lengths, use [`simulate`] for large machines.
+///
+/// # Errors
+///
+/// Returns [`GameError`] if the `config` is invalid.
+///
+/// # Examples
+///
+/// ```
+/// # use slot_machine::game::builder::GameBuilder;
+/// # use slot_machine::game::simulation::rtp;
+/// let exact = rtp(&GameBuilder::new()).unwrap();
+///
+/// assert!(exact.rtp > 0.9 && exact.rtp < 0.95);
+/// ```
+pub fn rtp(config: &GameBuilder) -> Result<ExactRtp, GameError> {
+    Ok(exact_rtp(&config.clone().build()?))
+}
+
+// Calculates the exact return to player of the `game`, see `rtp`.
+pub(crate) fn exact_rtp(game: &Game) -> ExactRtp {
+    let reels = &game.reels;
+    let mut stops = vec![0; reels.len()];
+    let mut total_weight: u128 = 1;
+    let mut total_pays: u128 = 0;
+    let mut hit_weight: u128 = 0;
+    let mut rule_pays: BTreeMap<&str, u128> = BTreeMap::new();
+    let lines = game.paylines.len() as u64;
+
+    for reel in reels {
+        total_weight *= u128::from(reel.total_weight());
+    }
+
+    // Number of scatters in the window of every stop of every reel
+    let scatters: Vec<Vec<usize>> = reels
+        .iter()
+        .map(|reel| {
+            (0..reel.len())
+                .map(|stop| {
+                    reel.window(stop, NUM_ROWS)
+                        .iter()
+                        .filter(|&&x| x == Symbol::Scatter)
+                        .count()
+                })
+                .collect()
+        })
+        .collect();
+
+    loop {
+        let weight: u128 = reels
+            .iter()
+            .zip(&stops)
+            .map(|(reel, &stop)| u128::from(reel.stops()[stop].weight))
+            .product();
+
+        if weight > 0 {
+            let count: usize = scatters.iter().zip(&stops).map(|(x, &stop)| x[stop]).sum();
+            // Scatters pay the total bet, convert to the bet per line
+            let scatter = game
+                .paytable
+                .scatters()
+                .iter()
+                .find(|rule| count >= rule.count);
+            let scatter_pays = scatter.map_or(0, |rule| u64::from(rule.pays) * lines);
+            if let Some(rule) = scatter {
+                *rule_pays.entry(&rule.name).or_insert(0) += weight * u128::from(scatter_pays);
+            }
+
+            let pays: u64 = game
+                .paylines
+                .iter()
+                .map(|payline| {
+                    let symbols: Vec<_> = payline
+                        .rows()
+                        .iter()
+                        .zip(reels.iter().zip(&stops))
+                        .map(|(&row, (reel, &stop))| {
+                            reel.symbol(reel.row_index(stop, NUM_ROWS, row))
+                        })
+                        .collect();
+
+                    let mut line = 0;
+                    game.paytable.for_each_win(&symbols, |rule, pays| {
+                        *rule_pays.entry(&rule.name).or_insert(0) += weight * u128::from(pays);
+                        line += u64::from(pays);
+                    });
+
+                    line
+                })
+                .sum::<u64>()
+                + scatter_pays;
+
+            total_pays += weight * u128::from(pays);
+            if pays > 0 {
+                hit_weight += weight;
+            }
+        }
+
+        // Advances `stops` to the next combination like an odometer
+        let next = stops
+            .iter()
+            .zip(reels)
+            .position(|(&stop, reel)| stop + 1 < reel.len());
+        match next {
+            Some(reel) => {
+                stops[reel] += 1;
+                for stop in stops.iter_mut().take(reel) {
+                    *stop = 0;
+                }
+            }
+            None => break,
+        }
+    }
+
+    ExactRtp {
+        rtp: total_pays as f64 / total_weight as f64 / lines as f64,
+        hit_frequency: hit_weight as f64 / total_weight as f64,
+        contributions: rule_pays
+            .into_iter()
+            .map(|(rule, pays)| {
+                let share = pays as f64 / total_weight as f64 / lines as f64;
+                (rule.to_owned(), share)
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::payline::Payline;
+    use crate::game::paytable::{Paytable, ScatterRule};
+    use crate::game::reel::{ReelStrip, Stop};
+    use crate::game::NUM_REELS;
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn simulate_seeded() {
+        let config = GameBuilder::new().rng(StdRng::seed_from_u64(5));
+
+        assert_eq!(
+            simulate(&config, 1000).unwrap(),
+            simulate(&config, 1000).unwrap()
+        );
+    }
+
+    #[test]
+    fn simulate_fixed_reels() {
+        let sevens = ReelStrip::new(vec![Stop {
+            symbol: Symbol::Seven,
+            weight: 1,
+        }]);
+        let config = GameBuilder::new()
+            .bet(2, 1, 10)
+            .reels(vec![sevens; NUM_REELS]);
+
+        let report = simulate(&config, 100).unwrap();
+
+        assert_eq!(report.total_bet, 200);
+        assert_eq!(report.rtp(), 300.0);
+        assert_eq!(report.hit_frequency(), 1.0);
+        assert_eq!(report.max_win, 600);
+        assert_eq!(report.histogram.get(&600), Some(&100));
+    }
+
+    #[test]
+    fn rtp_matches_virtual_reel() {
+        let mut pays = 0;
+        let mut hits = 0;
+        for a in Symbol::ALL.iter() {
+            for b in Symbol::ALL.iter() {
+                for c in Symbol::ALL.iter() {
+                    let weight = a.weight() * b.weight() * c.weight();
+                    let payout = crate::game::payout::payout(&[*a, *b, *c]);
+
+                    pays += u64::from(weight * payout);
+                    if payout > 0 {
+                        hits += u64::from(weight);
+                    }
+                }
+            }
+        }
+        let combinations = 128.0 * 128.0 * 128.0;
+
+        let exact = rtp(&GameBuilder::new()).unwrap();
+
+        assert!((exact.rtp - pays as f64 / combinations).abs() < 1e-12);
+        assert!((exact.hit_frequency - hits as f64 / combinations).abs() < 1e-12);
+        assert!((exact.contributions.values().sum::<f64>() - exact.rtp).abs() < 1e-12);
+        assert!((exact.contributions["3 Seven"] - 0.073242).abs() < 1e-6);
+    }
+
+    #[test]
+    fn rtp_close_to_simulation() {
+        let config = GameBuilder::new().rng(StdRng::seed_from_u64(2));
+
+        let report = simulate(&config, 200_000).unwrap();
+        let exact = rtp(&config).unwrap();
+
+        assert!((report.rtp() - exact.rtp).abs() < 0.03);
+        assert!((report.hit_frequency() - exact.hit_frequency).abs() < 0.01);
+        assert_eq!(report.contributions.total(), report.total_win);
+    }
+
+    #[test]
+    fn scatters_pay_total_bet() {
+        let scatters = ReelStrip::new(vec![
+            Stop {
+                symbol: Symbol::Scatter,
+                weight: 1,
+            },
+            Stop {
+                symbol: Symbol::Blank,
+                weight: 3,
+            },
+        ]);
+        let mut paytable = Paytable::default();
+        paytable.set_scatters(vec![ScatterRule {
+            name: "Scatter".to_owned(),
+            count: 1,
+            pays: 2,
+        }]);
+        let config = GameBuilder::new()
+            .paytable(paytable)
+            .reels(vec![scatters; NUM_REELS])
+            .paylines(vec![
+                Payline::horizontal(NUM_REELS, 0),
+                Payline::horizontal(NUM_REELS, 2),
+            ]);
+
+        let report = simulate(&config, 100).unwrap();
+        let exact = rtp(&config).unwrap();
+
+        // Every window of a 2-stop reel shows a scatter
+        assert_eq!(report.rtp(), 2.0);
+        assert_eq!(exact.rtp, 2.0);
+        assert_eq!(exact.hit_frequency, 1.0);
+    }
+
+    #[test]
+    fn simulator_resume() {
+        let config = GameBuilder::new().bet(2, 1, 10);
+        let mut simulator = Simulator::new(&config, 9).unwrap();
+        simulator.run(CHECKPOINT_INTERVAL + 500).unwrap();
+
+        let path = env::temp_dir().join("slot_machine_checkpoint_test.bin");
+        simulator
+            .checkpoint()
+            .save_to_file(&path, SaveFormat::Bincode)
+            .unwrap();
+        let checkpoint = Checkpoint::load_from_file(&path, SaveFormat::Bincode).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(checkpoint.report().spins, CHECKPOINT_INTERVAL);
+
+        let mut resumed = Simulator::resume(checkpoint).unwrap();
+        resumed.run(1000).unwrap();
+        simulator.run(500).unwrap();
+
+        assert_eq!(resumed.report(), simulator.report());
+    }
+
+    #[test]
+    fn block_rng_independent_seeds() {
+        use rand::RngCore;
+
+        let stream = |seed, block| {
+            let mut rng = block_rng(seed, block);
+            (0..4).map(|_| rng.next_u64()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(stream(7, 1), stream(7, 1));
+        assert_ne!(stream(7, 1), stream(8, 0));
+        assert_ne!(stream(7, 0), stream(8, 0));
+        assert_ne!(stream(7, 0), stream(7, 1));
+
+        let mut a = Simulator::new(&GameBuilder::new(), 7).unwrap();
+        let mut b = Simulator::new(&GameBuilder::new(), 8).unwrap();
+        a.run(1000).unwrap();
+        b.run(1000).unwrap();
+        assert_ne!(a.report(), b.report());
+    }
+
+    #[test]
+    fn simulator_corrupted_checkpoint() {
+        let simulator = Simulator::new(&GameBuilder::new(), 1).unwrap();
+        let mut checkpoint = simulator.checkpoint().clone();
+        checkpoint.report.total_win += 1;
+
+        let error = Simulator::resume(checkpoint).unwrap_err();
+
+        assert!(matches!(error, GameError::Serialization(_)));
+    }
+
+    #[test]
+    fn simulate_parallel_any_threads() {
+        let config = GameBuilder::new().bet(2, 1, 10);
+        let spins = CHECKPOINT_INTERVAL + 123;
+
+        let report = simulate_parallel(&config, spins, 11, 1).unwrap();
+
+        assert_eq!(report.spins, spins);
+        assert_eq!(simulate_parallel(&config, spins, 11, 2).unwrap(), report);
+        assert_eq!(simulate_parallel(&config, spins, 11, 8).unwrap(), report);
+    }
+
+    #[test]
+    fn simulate_parallel_reproducible() {
+        // Published numbers must not change between platforms and versions of the crate
+        let report = simulate_parallel(&GameBuilder::new(), 10_000, 42, 2).unwrap();
+
+        assert_eq!(report.total_bet, 10_000);
+        assert_eq!(report.total_win, 9945);
+        assert_eq!(report.hits, 1485);
+    }
+
+    #[test]
+    fn simulate_invalid_config() {
+        let config = GameBuilder::new().bet(0, 1, 10);
+
+        assert!(simulate(&config, 1).is_err());
+    }
+}
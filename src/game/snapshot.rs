@@ -0,0 +1,167 @@
+use crate::game::bonus::GameMode;
+use crate::game::wallet::Wallet;
+use crate::game::Game;
+use serde_derive::{Deserialize, Serialize};
+use std::fmt;
+
+/// A cheap, comparable copy of the [`Game`] meters.
+///
+/// Useful for debugging client/server state desyncs and for checking invariants between spins.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameSnapshot {
+    /// Balance
+    pub credits: u32,
+    /// Actual bet size
+    pub bet: u32,
+    /// Minimum bet size
+    pub bet_min: u32,
+    /// Maximum bet size
+    pub bet_max: u32,
+    /// The amount of the last win
+    pub win: u32,
+    /// Base game or free spins with the number of remaining spins
+    #[serde(default)]
+    pub mode: GameMode,
+    /// Size of the progressive jackpot pool, `None` if the game has no jackpot
+    #[serde(default)]
+    pub jackpot_pool: Option<u32>,
+}
+
+/// A field whose value differs between two [`GameSnapshot`]s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldChange {
+    /// Name of the changed field
+    pub field: &'static str,
+    /// Value in the original snapshot
+    pub old: String,
+    /// Value in the other snapshot
+    pub new: String,
+}
+
+impl fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} -> {}", self.field, self.old, self.new)
+    }
+}
+
+impl GameSnapshot {
+    /// Returns the list of fields that differ between `self` and `other`.
+    ///
+    /// The list is empty if the snapshots are equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use slot_machine::game::paytable::Paytable;
+    /// # use slot_machine::game::Game;
+    /// let mut game = Game::new(1000, 1, 1, 100, Paytable::default()).unwrap();
+    /// let before = game.snapshot();
+    ///
+    /// game.set_bet(5).unwrap();
+    /// let changes = before.diff(&game.snapshot());
+    ///
+    /// assert_eq!(changes.len(), 1);
+    /// assert_eq!(changes[0].to_string(), "bet: 1 -> 5");
+    /// ```
+    pub fn diff(&self, other: &GameSnapshot) -> Vec<FieldChange> {
+        let mut changes = Vec::new();
+
+        push_change(&mut changes, "credits", &self.credits, &other.credits);
+        push_change(&mut changes, "bet", &self.bet, &other.bet);
+        push_change(&mut changes, "bet_min", &self.bet_min, &other.bet_min);
+        push_change(&mut changes, "bet_max", &self.bet_max, &other.bet_max);
+        push_change(&mut changes, "win", &self.win, &other.win);
+        push_change(&mut changes, "mode", &self.mode, &other.mode);
+        push_change(
+            &mut changes,
+            "jackpot_pool",
+            &pool(self.jackpot_pool),
+            &pool(other.jackpot_pool),
+        );
+
+        changes
+    }
+}
+
+impl<W: Wallet> From<&Game<W>> for GameSnapshot {
+    fn from(game: &Game<W>) -> Self {
+        game.meters(game.wallet.balance())
+    }
+}
+
+// Adds a `FieldChange` to `changes` if `old` and `new` differ.
+fn push_change<T>(changes: &mut Vec<FieldChange>, field: &'static str, old: &T, new: &T)
+where
+    T: PartialEq + fmt::Display,
+{
+    if old != new {
+        changes.push(FieldChange {
+            field,
+            old: old.to_string(),
+            new: new.to_string(),
+        });
+    }
+}
+
+// Formats the jackpot `pool` for a `FieldChange`.
+fn pool(pool: Option<u32>) -> String {
+    pool.map_or_else(|| "none".to_owned(), |x| x.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::jackpot::Jackpot;
+    use crate::game::paytable::Paytable;
+
+    #[test]
+    fn snapshot_from_game() {
+        let game = Game::new(1000, 2, 1, 10, Paytable::default()).unwrap();
+
+        assert_eq!(
+            GameSnapshot {
+                credits: 1000,
+                bet: 2,
+                bet_min: 1,
+                bet_max: 10,
+                win: 0,
+                mode: GameMode::BaseGame,
+                jackpot_pool: None,
+            },
+            game.snapshot()
+        )
+    }
+
+    #[test]
+    fn diff_jackpot_pool() {
+        let mut game = Game::new(1000, 1, 1, 10, Paytable::default()).unwrap();
+        let before = game.snapshot();
+
+        game.set_jackpot(Some(Jackpot::new(500, 1)));
+        let changes = before.diff(&game.snapshot());
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].to_string(), "jackpot_pool: none -> 500");
+    }
+
+    #[test]
+    fn diff_equal_snapshots() {
+        let game = Game::new(1000, 1, 1, 10, Paytable::default()).unwrap();
+
+        assert!(game.snapshot().diff(&game.snapshot()).is_empty())
+    }
+
+    #[test]
+    fn diff_after_spin() {
+        let mut game = Game::new(1000, 1, 1, 10, Paytable::default()).unwrap();
+        let before = game.snapshot();
+
+        game.spin().unwrap();
+        let changes = before.diff(&game.snapshot());
+
+        assert!(changes
+            .iter()
+            .all(|x| x.field == "credits" || x.field == "win"));
+        assert!(changes.iter().all(|x| x.old != x.new));
+    }
+}
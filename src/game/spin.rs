@@ -0,0 +1,106 @@
+use crate::game::bonus::GameMode;
+use crate::game::symbol::Symbol;
+use crate::game::window::{Position, Window};
+use serde_derive::{Deserialize, Serialize};
+
+/// A winning payline.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LineWin {
+    /// Index of the payline in [`Game::paylines`]
+    ///
+    /// [`Game::paylines`]: crate::game::Game::paylines
+    pub line: usize,
+    /// Symbols on the payline
+    pub symbols: Vec<Symbol>,
+    /// Window positions of the symbols forming the combination
+    #[serde(default)]
+    pub positions: Vec<Position>,
+    /// Name of the winning paytable rule
+    pub combination: String,
+    /// The amount of the win on the line
+    pub win: u32,
+    /// `true` if the line won the progressive jackpot instead of the paytable payout
+    #[serde(default)]
+    pub jackpot: bool,
+}
+
+/// A win for [`Symbol::Scatter`] anywhere in the window.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScatterWin {
+    /// Number of scatters in the window
+    pub count: usize,
+    /// Window positions of the scatters
+    #[serde(default)]
+    pub positions: Vec<Position>,
+    /// Name of the winning scatter rule
+    pub combination: String,
+    /// The amount of the win
+    pub win: u32,
+}
+
+/// What a window pays, without a spin or a balance, see [`Paytable::evaluate_window`].
+///
+/// [`Paytable::evaluate_window`]: crate::game::paytable::Paytable::evaluate_window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Evaluation {
+    /// Winning paylines
+    pub lines: Vec<LineWin>,
+    /// Scatter win, if any
+    pub scatter: Option<ScatterWin>,
+    /// The amount of the win, the sum of wins on all lines and the scatter win
+    pub win: u32,
+}
+
+/// Outcome of a single spin.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpinResult {
+    /// Symbols visible on the reels
+    pub window: Window,
+    /// Total bet of the spin, the bet per line multiplied by the number of paylines,
+    /// 0 for a free spin
+    pub bet: u32,
+    /// The amount of the win, the sum of wins on all lines and the scatter win
+    pub win: u32,
+    /// Winning paylines
+    pub lines: Vec<LineWin>,
+    /// Scatter win, if any
+    #[serde(default)]
+    pub scatter: Option<ScatterWin>,
+    /// Multiplier applied to the paytable wins, greater than 1 during free spins
+    pub multiplier: u32,
+    /// Mode of the game after the spin
+    pub mode: GameMode,
+    /// Balance after the spin
+    pub credits: u32,
+}
+
+impl SpinResult {
+    /// Returns `true` if the spin won anything
+    pub fn is_win(&self) -> bool {
+        self.win > 0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::symbol::Symbol::*;
+
+    #[test]
+    fn is_win() {
+        let mut result = SpinResult {
+            window: Window::from_columns(vec![vec![Bar, Blank, Seven]; 3]),
+            bet: 1,
+            win: 0,
+            lines: vec![],
+            scatter: None,
+            multiplier: 1,
+            mode: GameMode::BaseGame,
+            credits: 99,
+        };
+        assert!(!result.is_win());
+
+        result.win = 3;
+        assert!(result.is_win());
+    }
+}
@@ -8,7 +8,7 @@ use serde_derive::{Deserialize, Serialize};
 use std::ops::RangeInclusive;
 
 /// Symbols
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Symbol {
     Blank,
     Cherry,
@@ -17,6 +17,28 @@ pub enum Symbol {
     TripleBar,
     Seven,
     Jackpot,
+    /// Pays anywhere in the window, never appears on the virtual reel
+    Scatter,
+    /// Substitutes for paying symbols if the paytable has a [`WildRule`],
+    /// never appears on the virtual reel
+    ///
+    /// [`WildRule`]: crate::game::paytable::WildRule
+    Wild,
+}
+
+/// Role of a symbol in the game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SymbolCategory {
+    /// Pays nothing on its own
+    Blank,
+    /// Pays on the paylines
+    Regular,
+    /// Pays the top award and the progressive jackpot
+    Jackpot,
+    /// Pays anywhere in the window
+    Scatter,
+    /// Substitutes for other symbols
+    Wild,
 }
 
 impl fmt::Display for Symbol {
@@ -29,6 +51,11 @@ impl Symbol {
     /// The range of numbers for which there are corresponding symbols.
     pub const RANGE: RangeInclusive<u32> = 0..=127;
 
+    /// All symbols in ascending order of value.
+    pub const ALL: [Symbol; 9] = [
+        Blank, Cherry, Bar, DoubleBar, TripleBar, Seven, Jackpot, Scatter, Wild,
+    ];
+
     /// Searches for the corresponding [`Symbol`] in the range [`RANGE`] for `number`.
     ///
     /// Returns `None` if the `number` is not in the [`Symbol::RANGE`].
@@ -55,6 +82,19 @@ impl Symbol {
         Some(symbol)
     }
 
+    /// Returns the number of virtual stops in [`Symbol::RANGE`] mapped to the symbol.
+    ///
+    /// # Examples
+    /// ```
+    /// # use slot_machine::game::symbol::Symbol;
+    /// assert_eq!(Symbol::Jackpot.weight(), 2);
+    /// ```
+    pub fn weight(&self) -> u32 {
+        Self::RANGE
+            .filter(|&number| Symbol::from_number(number).as_ref() == Some(self))
+            .count() as u32
+    }
+
     /// Returns a random [`Symbol`]
     ///
     /// # Examples
@@ -69,6 +109,24 @@ impl Symbol {
 
         Symbol::from_number(number).unwrap()
     }
+
+    /// Returns the role of the symbol in the game.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use slot_machine::game::symbol::{Symbol, SymbolCategory};
+    /// assert_eq!(Symbol::Seven.category(), SymbolCategory::Regular);
+    /// ```
+    pub fn category(&self) -> SymbolCategory {
+        match self {
+            Blank => SymbolCategory::Blank,
+            Jackpot => SymbolCategory::Jackpot,
+            Scatter => SymbolCategory::Scatter,
+            Wild => SymbolCategory::Wild,
+            _ => SymbolCategory::Regular,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -117,6 +175,13 @@ mod test {
         assert_eq!(Symbol::from_number(127).unwrap(), Symbol::Jackpot);
     }
 
+    #[test]
+    fn weights_cover_range() {
+        let total: u32 = Symbol::ALL.iter().map(|x| x.weight()).sum();
+
+        assert_eq!(total, Symbol::RANGE.end() - Symbol::RANGE.start() + 1);
+    }
+
     #[test]
     fn from_number_assert_error() {
         assert_eq!(Symbol::from_number(128), None);
@@ -0,0 +1,199 @@
+use crate::game::error::{ConfigError, GameError};
+use crate::game::symbol::{Symbol, SymbolCategory};
+use crate::game::window::Window;
+use serde_derive::{Deserialize, Serialize};
+
+/// A themed symbol of a [`SymbolSet`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SymbolDef {
+    /// Unique name of the symbol, e.g. `Lemon`
+    pub id: String,
+    /// Text shown to the player, e.g. `🍋`
+    pub glyph: String,
+    /// The built-in symbol the themed symbol plays as on the reels and in the paytable
+    pub symbol: Symbol,
+}
+
+impl SymbolDef {
+    /// Returns the role of the symbol in the game
+    pub fn category(&self) -> SymbolCategory {
+        self.symbol.category()
+    }
+}
+
+/// Registry of themed symbols, maps the built-in [`Symbol`]s to their names and glyphs.
+///
+/// A symbol set only relabels: the reels, the paytable, save files and the REST API always use
+/// the built-in symbols, and the themed ids are not accepted there. Every themed symbol plays as
+/// a distinct built-in symbol, so a set has at most [`Symbol::ALL`] symbols and cannot add new ones.
+/// Symbols missing from the set are shown by their built-in name. The default set is the built-in one.
+///
+/// # Examples
+///
+/// ```
+/// # use slot_machine::game::symbol::Symbol;
+/// # use slot_machine::game::symbol_set::SymbolSet;
+/// let fruits = SymbolSet::from_toml(
+///     r#"
+///     [[symbols]]
+///     id = "Lemon"
+///     glyph = "🍋"
+///     symbol = "Cherry"
+///
+///     [[symbols]]
+///     id = "Bell"
+///     glyph = "🔔"
+///     symbol = "Seven"
+///     "#,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(fruits.find("Bell").unwrap().symbol, Symbol::Seven);
+/// assert_eq!(fruits.glyph(Symbol::Cherry), "🍋");
+/// assert_eq!(fruits.glyph(Symbol::Bar), "Bar");
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SymbolSet {
+    symbols: Vec<SymbolDef>,
+}
+
+impl SymbolSet {
+    /// Creates new [`SymbolSet`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::Symbols`] if an id or a built-in symbol is used twice.
+    pub fn new(symbols: Vec<SymbolDef>) -> Result<Self, GameError> {
+        for (index, def) in symbols.iter().enumerate() {
+            let duplicate = symbols[..index]
+                .iter()
+                .any(|x| x.id == def.id || x.symbol == def.symbol);
+
+            if duplicate {
+                return Err(ConfigError::Symbols(def.id.clone()).into());
+            }
+        }
+
+        Ok(SymbolSet { symbols })
+    }
+
+    /// Deserializes [`SymbolSet`] from a TOML string.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GameError::Serialization`] if `toml` is not a valid symbol set,
+    /// [`ConfigError::Symbols`] if an id or a built-in symbol is used twice.
+    pub fn from_toml(toml: &str) -> Result<Self, GameError> {
+        #[derive(Deserialize)]
+        struct Raw {
+            symbols: Vec<SymbolDef>,
+        }
+
+        let raw: Raw = toml::from_str(toml)?;
+
+        SymbolSet::new(raw.symbols)
+    }
+
+    /// Returns the themed symbols
+    pub fn symbols(&self) -> &[SymbolDef] {
+        &self.symbols
+    }
+
+    /// Returns the themed symbol with the `id`
+    pub fn find(&self, id: &str) -> Option<&SymbolDef> {
+        self.symbols.iter().find(|x| x.id == id)
+    }
+
+    /// Returns the themed symbol playing as the built-in `symbol`
+    pub fn get(&self, symbol: Symbol) -> Option<&SymbolDef> {
+        self.symbols.iter().find(|x| x.symbol == symbol)
+    }
+
+    /// Returns the glyph of the `symbol`, its built-in name if the set does not define it.
+    pub fn glyph(&self, symbol: Symbol) -> String {
+        self.get(symbol)
+            .map_or_else(|| symbol.to_string(), |x| x.glyph.clone())
+    }
+
+    /// Returns the glyphs of the `window`, one line per row separated by spaces.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use slot_machine::game::symbol::Symbol::*;
+    /// # use slot_machine::game::symbol_set::SymbolSet;
+    /// # use slot_machine::game::window::Window;
+    /// let window = Window::from_columns(vec![vec![Seven, Bar], vec![Cherry, Blank]]);
+    ///
+    /// assert_eq!(SymbolSet::default().render(&window), "7 C\nB -");
+    /// ```
+    pub fn render(&self, window: &Window) -> String {
+        (0..window.rows())
+            .map(|row| {
+                window
+                    .row(row)
+                    .iter()
+                    .map(|&x| self.glyph(x))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl Default for SymbolSet {
+    /// The built-in symbols.
+    fn default() -> Self {
+        let glyphs = ["-", "C", "B", "BB", "BBB", "7", "J", "S", "W"];
+
+        SymbolSet {
+            symbols: Symbol::ALL
+                .iter()
+                .zip(glyphs.iter())
+                .map(|(&symbol, &glyph)| SymbolDef {
+                    id: symbol.to_string(),
+                    glyph: glyph.to_owned(),
+                    symbol,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn default_covers_all_symbols() {
+        let set = SymbolSet::default();
+
+        assert_eq!(set.symbols().len(), Symbol::ALL.len());
+        assert!(Symbol::ALL.iter().all(|&x| set.get(x).is_some()));
+        assert_eq!(set.find("Wild").unwrap().category(), SymbolCategory::Wild);
+        assert_eq!(SymbolSet::new(set.symbols().to_vec()).unwrap(), set);
+    }
+
+    #[test]
+    fn new_duplicate() {
+        let def = |id: &str, symbol| SymbolDef {
+            id: id.to_owned(),
+            glyph: id.to_owned(),
+            symbol,
+        };
+
+        let same_id = SymbolSet::new(vec![def("Bell", Symbol::Seven), def("Bell", Symbol::Bar)]);
+        let same_symbol =
+            SymbolSet::new(vec![def("Bell", Symbol::Seven), def("Plum", Symbol::Seven)]);
+
+        assert!(matches!(
+            same_id,
+            Err(GameError::InvalidConfig(ConfigError::Symbols(ref id))) if id == "Bell"
+        ));
+        assert!(matches!(
+            same_symbol,
+            Err(GameError::InvalidConfig(ConfigError::Symbols(ref id))) if id == "Plum"
+        ));
+    }
+}
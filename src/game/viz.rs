@@ -0,0 +1,205 @@
+use crate::game::paytable::Paytable;
+use crate::game::reel::ReelStrip;
+use crate::game::symbol::Symbol;
+use crate::game::symbol_set::SymbolSet;
+use std::fmt::Write;
+
+// Size of a reel row in pixels, the bands of the stops share the width.
+const REEL_WIDTH: f64 = 600.0;
+const REEL_HEIGHT: f64 = 48.0;
+// Width of the captions on the left of the reels and of a paytable column.
+const CAPTION: f64 = 160.0;
+const GAP: f64 = 20.0;
+// Height of a paytable row in pixels.
+const LINE: f64 = 24.0;
+// Bands narrower than this are not labelled.
+const MIN_LABEL: f64 = 32.0;
+
+// Fill of the bands, one color per built-in symbol in the order of `Symbol::ALL`.
+const COLORS: [&str; 9] = [
+    "#e0e0e0", "#e53935", "#1e88e5", "#3949ab", "#5e35b1", "#fdd835", "#fb8c00", "#43a047",
+    "#8e24aa",
+];
+
+/// Renders the `reels` as an SVG diagram, one row per reel from the first to the last.
+///
+/// Every stop is a band in the order of the strip, the width of the band is proportional
+/// to the weight of the stop. The bands are labelled with the glyphs of the `symbols`
+/// and the weights.
+///
+/// # Examples
+///
+/// ```
+/// # use slot_machine::game::reel::ReelStrip;
+/// # use slot_machine::game::symbol_set::SymbolSet;
+/// # use slot_machine::game::viz::reels_svg;
+/// let svg = reels_svg(&vec![ReelStrip::default(); 3], &SymbolSet::default());
+///
+/// assert!(svg.starts_with("<svg"));
+/// ```
+pub fn reels_svg(reels: &[ReelStrip], symbols: &SymbolSet) -> String {
+    let width = CAPTION + REEL_WIDTH + GAP;
+    let height = reels.len() as f64 * (REEL_HEIGHT + GAP) + GAP;
+    let mut svg = header(width, height);
+
+    for (index, reel) in reels.iter().enumerate() {
+        let y = GAP + index as f64 * (REEL_HEIGHT + GAP);
+        let middle = y + REEL_HEIGHT / 2.0 + 5.0;
+        text(
+            &mut svg,
+            CAPTION / 2.0,
+            middle,
+            &format!("Reel {}", index + 1),
+        );
+
+        let total = f64::from(reel.total_weight());
+        let mut x = CAPTION;
+        for stop in reel.stops() {
+            let band = f64::from(stop.weight) / total * REEL_WIDTH;
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="{}" stroke="white"/>"#,
+                x,
+                y,
+                band,
+                REEL_HEIGHT,
+                color(stop.symbol)
+            );
+            if band >= MIN_LABEL {
+                let label = format!("{} {}", symbols.glyph(stop.symbol), stop.weight);
+                text(&mut svg, x + band / 2.0, middle, &label);
+            }
+            x += band;
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Renders the `paytable` as an SVG table: the line rules, the scatters and the wild.
+///
+/// # Examples
+///
+/// ```
+/// # use slot_machine::game::paytable::Paytable;
+/// # use slot_machine::game::viz::paytable_svg;
+/// let svg = paytable_svg(&Paytable::default());
+///
+/// assert!(svg.contains("3 Jackpot"));
+/// ```
+pub fn paytable_svg(paytable: &Paytable) -> String {
+    let mut rows: Vec<(String, String)> = paytable
+        .rules()
+        .iter()
+        .map(|x| (x.name.clone(), format!("x{}", x.pays)))
+        .collect();
+    rows.extend(
+        paytable
+            .scatters()
+            .iter()
+            .map(|x| (x.name.clone(), format!("x{} total bet", x.pays))),
+    );
+    if let Some(wild) = paytable.wild() {
+        rows.push(("Wild".to_owned(), format!("wins x{}", wild.multiplier)));
+    }
+
+    let width = 2.0 * (CAPTION + GAP) + GAP;
+    let height = (rows.len() + 1) as f64 * LINE + GAP;
+    let mut svg = header(width, height);
+    let left = GAP + CAPTION / 2.0;
+    let right = left + CAPTION + GAP;
+
+    text(&mut svg, left, LINE - 6.0, "Combination");
+    text(&mut svg, right, LINE - 6.0, "Pays");
+    for (index, (name, pays)) in rows.iter().enumerate() {
+        let y = (index + 2) as f64 * LINE - 6.0;
+        text(&mut svg, left, y, name);
+        text(&mut svg, right, y, pays);
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+// Opens an SVG document of `width` x `height` pixels.
+fn header(width: f64, height: f64) -> String {
+    format!(
+        concat!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w:.0}" height="{h:.0}" "#,
+            r#"viewBox="0 0 {w:.0} {h:.0}" font-family="sans-serif" font-size="14">"#,
+            "\n"
+        ),
+        w = width,
+        h = height
+    )
+}
+
+// Appends the `content` centered at `x` with the baseline at `y`.
+fn text(svg: &mut String, x: f64, y: f64, content: &str) {
+    let _ = writeln!(
+        svg,
+        r#"<text x="{:.1}" y="{:.1}" text-anchor="middle">{}</text>"#,
+        x,
+        y,
+        escape(content)
+    );
+}
+
+// Returns the fill of the bands of the `symbol`.
+fn color(symbol: Symbol) -> &'static str {
+    let index = Symbol::ALL.iter().position(|&x| x == symbol).unwrap_or(0);
+
+    COLORS[index % COLORS.len()]
+}
+
+// Escapes the characters with a meaning in XML, names and glyphs come from user files.
+fn escape(content: &str) -> String {
+    content
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::paytable::{Combination, Rule};
+    use crate::game::reel::Stop;
+
+    #[test]
+    fn reels_bands() {
+        let reel = ReelStrip::new(vec![
+            Stop {
+                symbol: Symbol::Seven,
+                weight: 1,
+            },
+            Stop {
+                symbol: Symbol::Blank,
+                weight: 3,
+            },
+        ]);
+
+        let svg = reels_svg(&[reel.clone(), reel], &SymbolSet::default());
+
+        assert_eq!(svg.matches("<rect").count(), 4);
+        assert!(svg.contains(r##"width="150.0" height="48.0" fill="#fdd835""##));
+        assert!(svg.contains(r##"width="450.0" height="48.0" fill="#e0e0e0""##));
+        assert!(svg.contains(">7 1</text>"));
+    }
+
+    #[test]
+    fn paytable_escaped() {
+        let paytable = Paytable::new(vec![Rule {
+            name: "<Bars & Sevens>".to_owned(),
+            combination: Combination::AnyOf(vec![Symbol::Bar]),
+            pays: 5,
+        }]);
+
+        let svg = paytable_svg(&paytable);
+
+        assert!(svg.contains("&lt;Bars &amp; Sevens&gt;"));
+        assert!(svg.contains(">x5</text>"));
+    }
+}
@@ -0,0 +1,187 @@
+use crate::game::error::GameError;
+use serde_derive::{Deserialize, Serialize};
+#[cfg(feature = "async")]
+use std::future::Future;
+
+/// Balance of a player in credits.
+///
+/// Implement this trait to keep the balance outside of the [`Game`], e.g. in a database,
+/// so every bet and win goes through your own transaction layer.
+/// Report failures of the backend as [`GameError::Wallet`], with a retry hint if the failure
+/// is transient, e.g. a timeout.
+///
+/// # Examples
+///
+/// ```
+/// # use slot_machine::game::error::GameError;
+/// # use slot_machine::game::paytable::Paytable;
+/// # use slot_machine::game::wallet::Wallet;
+/// # use slot_machine::game::Game;
+/// // A wallet logging every transaction
+/// struct LoggingWallet(u32);
+///
+/// impl Wallet for LoggingWallet {
+///     fn balance(&self) -> u32 {
+///         self.0
+///     }
+///
+///     fn debit(&mut self, amount: u32) -> Result<(), GameError> {
+///         println!("debit {}", amount);
+///         self.0 = self.0.checked_sub(amount).ok_or(GameError::LowBalance)?;
+///         Ok(())
+///     }
+///
+///     fn credit(&mut self, amount: u32) -> Result<(), GameError> {
+///         println!("credit {}", amount);
+///         self.0 += amount;
+///         Ok(())
+///     }
+/// }
+///
+/// let game = Game::new(0, 1, 1, 100, Paytable::default()).unwrap();
+/// let mut game = game.with_wallet(LoggingWallet(1000));
+/// game.spin().unwrap();
+/// ```
+///
+/// [`Game`]: crate::game::Game
+pub trait Wallet {
+    /// Returns the balance in credits
+    fn balance(&self) -> u32;
+
+    /// Takes `amount` credits from the balance.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GameError::LowBalance`] if the balance is less than `amount`.
+    fn debit(&mut self, amount: u32) -> Result<(), GameError>;
+
+    /// Adds `amount` credits to the balance.
+    fn credit(&mut self, amount: u32) -> Result<(), GameError>;
+}
+
+/// Balance kept by a remote service, debits and credits are awaited, see [`Game::spin_async`].
+///
+/// The balance is read without waiting, return the balance known after the last operation.
+/// The futures must be `Send`, so the game can be played in multithreaded runtimes
+/// such as tokio.
+///
+/// # Examples
+///
+/// ```
+/// # use slot_machine::game::error::GameError;
+/// # use slot_machine::game::paytable::Paytable;
+/// # use slot_machine::game::wallet::AsyncWallet;
+/// # use slot_machine::game::Game;
+/// // A wallet calling a payment service
+/// struct RemoteWallet {
+///     balance: u32,
+/// }
+///
+/// impl AsyncWallet for RemoteWallet {
+///     fn balance(&self) -> u32 {
+///         self.balance
+///     }
+///
+///     async fn debit(&mut self, amount: u32) -> Result<(), GameError> {
+///         // e.g. `self.balance = client.debit(amount).await?;`
+///         self.balance = self.balance.checked_sub(amount).ok_or(GameError::LowBalance)?;
+///         Ok(())
+///     }
+///
+///     async fn credit(&mut self, amount: u32) -> Result<(), GameError> {
+///         self.balance += amount;
+///         Ok(())
+///     }
+/// }
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let game = Game::new(0, 1, 1, 100, Paytable::default()).unwrap();
+/// let mut game = game.with_wallet(RemoteWallet { balance: 1000 });
+///
+/// let result = game.spin_async().await.unwrap();
+/// assert_eq!(result.credits, game.wallet().balance());
+/// # });
+/// ```
+///
+/// [`Game::spin_async`]: crate::game::Game::spin_async
+#[cfg(feature = "async")]
+pub trait AsyncWallet {
+    /// Returns the balance in credits
+    fn balance(&self) -> u32;
+
+    /// Takes `amount` credits from the balance.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GameError::LowBalance`] if the balance is less than `amount`.
+    fn debit(&mut self, amount: u32) -> impl Future<Output = Result<(), GameError>> + Send;
+
+    /// Adds `amount` credits to the balance.
+    fn credit(&mut self, amount: u32) -> impl Future<Output = Result<(), GameError>> + Send;
+}
+
+/// In-memory balance, the default [`Wallet`] of the [`Game`].
+///
+/// [`Game`]: crate::game::Game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct MemoryWallet(u32);
+
+impl MemoryWallet {
+    /// Creates new [`MemoryWallet`] with the `balance` in credits.
+    pub fn new(balance: u32) -> Self {
+        MemoryWallet(balance)
+    }
+}
+
+impl Wallet for MemoryWallet {
+    fn balance(&self) -> u32 {
+        self.0
+    }
+
+    fn debit(&mut self, amount: u32) -> Result<(), GameError> {
+        self.0 = self.0.checked_sub(amount).ok_or(GameError::LowBalance)?;
+
+        Ok(())
+    }
+
+    /// # Errors
+    ///
+    /// Returns [`GameError::Wallet`] if the balance would overflow.
+    fn credit(&mut self, amount: u32) -> Result<(), GameError> {
+        self.0 = self
+            .0
+            .checked_add(amount)
+            .ok_or_else(|| GameError::Wallet {
+                source: "the balance overflows".into(),
+                retry_after: None,
+            })?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn debit_and_credit() {
+        let mut wallet = MemoryWallet::new(10);
+
+        wallet.debit(4).unwrap();
+        wallet.credit(2).unwrap();
+
+        assert_eq!(wallet.balance(), 8);
+        assert!(matches!(wallet.debit(9), Err(GameError::LowBalance)));
+        assert_eq!(wallet.balance(), 8);
+    }
+
+    #[test]
+    fn credit_overflow() {
+        let mut wallet = MemoryWallet::new(u32::MAX);
+
+        assert!(matches!(wallet.credit(1), Err(GameError::Wallet { .. })));
+        assert_eq!(wallet.balance(), u32::MAX);
+    }
+}
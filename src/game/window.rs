@@ -0,0 +1,180 @@
+use crate::game::symbol::Symbol;
+use serde_derive::{Deserialize, Serialize};
+
+/// A cell of the [`Window`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Position {
+    /// Index of the reel, from the left
+    pub reel: usize,
+    /// Index of the row, from the top
+    pub row: usize,
+}
+
+/// Symbols visible on the reels after a spin.
+///
+/// The symbols are stored row by row in a single buffer, so a row is a contiguous slice.
+///
+/// # Examples
+///
+/// ```
+/// # use slot_machine::game::symbol::Symbol::*;
+/// # use slot_machine::game::window::Window;
+/// let window = Window::from_columns(vec![
+///     vec![Seven, Bar, Cherry],
+///     vec![Blank, Seven, Bar],
+/// ]);
+///
+/// assert_eq!(window.at(1, 2), Bar);
+/// assert_eq!(window.row(0), &[Seven, Blank]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Window {
+    reels: usize,
+    rows: usize,
+    symbols: Vec<Symbol>,
+}
+
+impl Window {
+    /// Creates new [`Window`] of `reels` x `rows` from the symbols listed row by row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of `symbols` is not `reels * rows`.
+    pub fn new(reels: usize, rows: usize, symbols: Vec<Symbol>) -> Self {
+        assert_eq!(
+            symbols.len(),
+            reels * rows,
+            "`symbols` must contain {} symbols! Contains: {}",
+            reels * rows,
+            symbols.len()
+        );
+
+        Window {
+            reels,
+            rows,
+            symbols,
+        }
+    }
+
+    /// Creates new [`Window`] from the columns, `columns[reel][row]` is the symbol on the `reel` in the `row`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the columns have different lengths.
+    pub fn from_columns(columns: Vec<Vec<Symbol>>) -> Self {
+        let reels = columns.len();
+        let rows = columns.first().map_or(0, |x| x.len());
+
+        assert!(
+            columns.iter().all(|x| x.len() == rows),
+            "`columns` must have the same length!"
+        );
+
+        let symbols = (0..rows)
+            .flat_map(|row| columns.iter().map(move |column| column[row]))
+            .collect();
+
+        Window::new(reels, rows, symbols)
+    }
+
+    /// Returns the number of reels
+    pub fn reels(&self) -> usize {
+        self.reels
+    }
+
+    /// Returns the number of rows
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the symbol on the `reel` in the `row`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `reel` or `row` is out of bounds.
+    pub fn at(&self, reel: usize, row: usize) -> Symbol {
+        assert!(reel < self.reels, "`reel` is out of bounds!");
+
+        self.row(row)[reel]
+    }
+
+    /// Returns the symbols in the `row`, one per reel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` is out of bounds.
+    pub fn row(&self, row: usize) -> &[Symbol] {
+        &self.symbols[row * self.reels..(row + 1) * self.reels]
+    }
+
+    /// Returns all symbols row by row
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    /// Returns the positions of the `symbol`, row by row.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use slot_machine::game::symbol::Symbol::*;
+    /// # use slot_machine::game::window::{Position, Window};
+    /// let window = Window::from_columns(vec![vec![Seven, Bar], vec![Bar, Cherry]]);
+    ///
+    /// assert_eq!(
+    ///     window.positions_of(Bar),
+    ///     vec![Position { reel: 1, row: 0 }, Position { reel: 0, row: 1 }]
+    /// );
+    /// ```
+    pub fn positions_of(&self, symbol: Symbol) -> Vec<Position> {
+        self.symbols
+            .iter()
+            .enumerate()
+            .filter(|(_, &x)| x == symbol)
+            .map(|(index, _)| Position {
+                reel: index % self.reels,
+                row: index / self.reels,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::symbol::Symbol::*;
+
+    #[test]
+    fn from_columns() {
+        let window = Window::from_columns(vec![vec![Seven, Bar], vec![Blank, Cherry]]);
+
+        assert_eq!(window.reels(), 2);
+        assert_eq!(window.rows(), 2);
+        assert_eq!(window.symbols(), &[Seven, Blank, Bar, Cherry]);
+        assert_eq!(window.at(0, 1), Bar);
+        assert_eq!(window.at(1, 0), Blank);
+    }
+
+    #[test]
+    fn positions_of() {
+        let window = Window::from_columns(vec![vec![Seven, Bar], vec![Blank, Seven]]);
+
+        assert_eq!(
+            window.positions_of(Seven),
+            vec![Position { reel: 0, row: 0 }, Position { reel: 1, row: 1 }]
+        );
+        assert!(window.positions_of(Cherry).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn at_out_of_bounds() {
+        Window::new(2, 1, vec![Seven, Bar]).at(2, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_wrong_size() {
+        Window::new(3, 3, vec![Seven; 8]);
+    }
+}
@@ -1 +1,36 @@
 pub mod game;
+pub mod prelude;
+
+/// Playing the game: spins, the window, symbols, bonuses and the balance.
+pub mod engine {
+    pub use crate::game::{
+        bonus, events, fairness, invariant, jackpot, ledger, spin, symbol, wallet, window, Game,
+        NUM_ROWS,
+    };
+}
+
+/// Configuring a game: reels, paylines, paytable, symbol sets and the errors of invalid setups.
+pub mod config {
+    pub use crate::game::{builder, error, payline, paytable, reel, symbol_set};
+}
+
+/// Measuring a game: simulations, exact RTP, diagnostics, shadow play and diagrams.
+pub mod stats {
+    pub use crate::game::{
+        contribution, diagnostics, footprint, history, payout, shadow, simulation,
+    };
+
+    #[cfg(feature = "viz")]
+    pub use crate::game::viz;
+}
+
+/// Persisting a game: save files, snapshots, the session store of the REST server and its client.
+pub mod io {
+    pub use crate::game::{save, snapshot};
+
+    #[cfg(any(feature = "server", feature = "client"))]
+    pub use crate::game::server;
+
+    #[cfg(feature = "client")]
+    pub use crate::game::client;
+}
@@ -0,0 +1,428 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use sha2::{Digest, Sha256};
+use slot_machine::game::builder::GameBuilder;
+use slot_machine::game::diagnostics::differential;
+use slot_machine::game::fairness;
+use slot_machine::game::paytable::Paytable;
+use slot_machine::game::save::SaveFormat;
+use slot_machine::game::simulation::{
+    rtp, simulate, simulate_parallel, Checkpoint, SimulationReport, Simulator, CHECKPOINT_INTERVAL,
+};
+use slot_machine::game::spin::SpinResult;
+use slot_machine::game::symbol_set::SymbolSet;
+use slot_machine::game::{Game, NUM_REELS};
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process;
+
+const USAGE: &str = "Usage:
+    slot_machine simulate --spins <NUMBER> [--seed <NUMBER>] [--paytable <FILE.toml>]
+        [--checkpoint <FILE> | --threads <NUMBER>]
+    slot_machine rtp [--paytable <FILE.toml>]
+    slot_machine differential --spins <NUMBER> [--seed <NUMBER>] [--paytable <FILE.toml>]
+    slot_machine verify --server-seed <SEED> --client-seed <SEED> --nonce <NUMBER>
+        [--commitment <HASH>] [--paytable <FILE.toml>]
+    slot_machine receipt --server-seed <SEED> --client-seed <SEED> --nonce <NUMBER>
+        [--commitment <HASH>] [--paytable <FILE.toml>]";
+
+// Options shared by the subcommands.
+struct Options {
+    spins: Option<u64>,
+    seed: Option<u64>,
+    checkpoint: Option<String>,
+    threads: Option<u64>,
+    paytable: Option<Paytable>,
+    server_seed: Option<String>,
+    client_seed: Option<String>,
+    nonce: Option<u64>,
+    commitment: Option<String>,
+    config: GameBuilder,
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let result = match args.first().map(String::as_str) {
+        Some("simulate") => parse_options(&args[1..]).and_then(run_simulation),
+        Some("rtp") => parse_options(&args[1..]).and_then(run_rtp),
+        Some("differential") => parse_options(&args[1..]).and_then(run_differential),
+        Some("verify") => parse_options(&args[1..]).and_then(run_verify),
+        Some("receipt") => parse_options(&args[1..]).and_then(run_receipt),
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
+
+// Parses the options following the subcommand.
+fn parse_options(args: &[String]) -> Result<Options, String> {
+    let mut spins = None;
+    let mut seed = None;
+    let mut checkpoint = None;
+    let mut threads = None;
+    let mut paytable = None;
+    let mut server_seed = None;
+    let mut client_seed = None;
+    let mut nonce = None;
+    let mut commitment = None;
+    let mut config = GameBuilder::new();
+
+    let mut iter = args.iter();
+    while let Some(option) = iter.next() {
+        let value = iter
+            .next()
+            .ok_or_else(|| format!("Missing value of `{}`!\n{}", option, USAGE))?;
+
+        match option.as_str() {
+            "--spins" => spins = Some(parse_number(option, value)?),
+            "--seed" => {
+                let number = parse_number(option, value)?;
+                seed = Some(number);
+                config = config.rng(StdRng::seed_from_u64(number));
+            }
+            "--checkpoint" => checkpoint = Some(value.clone()),
+            "--threads" => threads = Some(parse_number(option, value)?),
+            "--paytable" => {
+                let toml = fs::read_to_string(value).map_err(|e| format!("{}: {}", value, e))?;
+                let loaded = Paytable::from_toml(&toml).map_err(|e| format!("{}: {}", value, e))?;
+                config = config.paytable(loaded.clone());
+                paytable = Some(loaded);
+            }
+            "--server-seed" => server_seed = Some(value.clone()),
+            "--client-seed" => client_seed = Some(value.clone()),
+            "--nonce" => nonce = Some(parse_number(option, value)?),
+            "--commitment" => commitment = Some(value.clone()),
+            _ => return Err(format!("Unknown option `{}`!\n{}", option, USAGE)),
+        }
+    }
+
+    Ok(Options {
+        spins,
+        seed,
+        checkpoint,
+        threads,
+        paytable,
+        server_seed,
+        client_seed,
+        nonce,
+        commitment,
+        config,
+    })
+}
+
+// Runs the simulation and prints the report.
+fn run_simulation(options: Options) -> Result<(), String> {
+    let spins = options
+        .spins
+        .ok_or_else(|| format!("`--spins` is required!\n{}", USAGE))?;
+    let report = match (&options.checkpoint, options.threads) {
+        (Some(_), Some(_)) => {
+            return Err(format!(
+                "`--checkpoint` and `--threads` cannot be combined!\n{}",
+                USAGE
+            ))
+        }
+        (Some(path), None) => run_checkpointed(&options, spins, Path::new(path))?,
+        (None, Some(0)) => return Err("`--threads` must be positive!".to_owned()),
+        (None, Some(threads)) => {
+            let seed = options.seed.unwrap_or_else(rand::random);
+            simulate_parallel(&options.config, spins, seed, threads as usize)
+                .map_err(|e| e.to_string())?
+        }
+        (None, None) => simulate(&options.config, spins).map_err(|e| e.to_string())?,
+    };
+
+    println!("Spins:         {}", report.spins);
+    println!("Total bet:     {}", report.total_bet);
+    println!("Total win:     {}", report.total_win);
+    println!("RTP:           {:.4}%", report.rtp() * 100.0);
+    println!("Hit frequency: {:.4}%", report.hit_frequency() * 100.0);
+    println!("Max win:       {}", report.max_win);
+    println!();
+    println!("{:>10} {:>12} {:>10}", "Win", "Spins", "Share");
+    for (win, count) in &report.histogram {
+        println!(
+            "{:>10} {:>12} {:>9.4}%",
+            win,
+            count,
+            *count as f64 / report.spins as f64 * 100.0
+        );
+    }
+    println!();
+    println!("{:<20} {:>14} {:>10}", "Rule", "Win", "RTP");
+    let share = |win: u64| win as f64 / report.total_bet.max(1) as f64 * 100.0;
+    for (rule, &win) in &report.contributions.by_rule {
+        println!("{:<20} {:>14} {:>9.4}%", rule, win, share(win));
+    }
+    let jackpot = report.contributions.jackpot;
+    if jackpot > 0 {
+        println!(
+            "{:<20} {:>14} {:>9.4}%",
+            "Jackpot pool",
+            jackpot,
+            share(jackpot)
+        );
+    }
+
+    Ok(())
+}
+
+// Runs the simulation saving a checkpoint to `path` every `CHECKPOINT_INTERVAL` spins,
+// resumes from the checkpoint if the file exists. The seed and the paytable of a resumed
+// simulation come from the checkpoint, different ones in the options are rejected.
+// `spins` is the total including the checkpointed spins, fewer than those are rejected too.
+fn run_checkpointed(
+    options: &Options,
+    spins: u64,
+    path: &Path,
+) -> Result<SimulationReport, String> {
+    let error = |e| format!("{}: {}", path.display(), e);
+
+    let mut simulator = if path.exists() {
+        let checkpoint = Checkpoint::load_from_file(path, SaveFormat::Bincode).map_err(error)?;
+        if let Some(seed) = options.seed.filter(|&x| x != checkpoint.seed()) {
+            return Err(format!(
+                "{}: the checkpoint continues the seed {}, not {}",
+                path.display(),
+                checkpoint.seed(),
+                seed
+            ));
+        }
+        if let Some(paytable) = &options.paytable {
+            if paytable != checkpoint.game().paytable() {
+                return Err(format!(
+                    "{}: the checkpoint continues a different paytable",
+                    path.display()
+                ));
+            }
+        }
+        if checkpoint.report().spins > spins {
+            return Err(format!(
+                "{}: the checkpoint already has {} spins, more than {}",
+                path.display(),
+                checkpoint.report().spins,
+                spins
+            ));
+        }
+        eprintln!("Resuming after {} spins", checkpoint.report().spins);
+        Simulator::resume(checkpoint).map_err(error)?
+    } else {
+        let seed = options.seed.unwrap_or_else(rand::random);
+        Simulator::new(&options.config, seed).map_err(|e| e.to_string())?
+    };
+
+    while simulator.report().spins < spins {
+        let left = spins - simulator.report().spins;
+        simulator
+            .run(left.min(CHECKPOINT_INTERVAL))
+            .map_err(|e| e.to_string())?;
+
+        simulator
+            .checkpoint()
+            .save_to_file(path, SaveFormat::Bincode)
+            .map_err(error)?;
+    }
+
+    Ok(simulator.report().clone())
+}
+
+// Calculates and prints the exact return to player.
+fn run_rtp(options: Options) -> Result<(), String> {
+    let exact = rtp(&options.config).map_err(|e| e.to_string())?;
+
+    println!("RTP:           {:.4}%", exact.rtp * 100.0);
+    println!("Hit frequency: {:.4}%", exact.hit_frequency * 100.0);
+    println!();
+    println!("{:<20} {:>10}", "Rule", "RTP");
+    for (rule, share) in &exact.contributions {
+        println!("{:<20} {:>9.4}%", rule, share * 100.0);
+    }
+
+    Ok(())
+}
+
+// Compares the paytable with the legacy evaluator on random windows, fails on any mismatch.
+fn run_differential(options: Options) -> Result<(), String> {
+    let spins = options
+        .spins
+        .ok_or_else(|| format!("`--spins` is required!\n{}", USAGE))?;
+    let seed = options.seed.unwrap_or_else(rand::random);
+    let game = options.config.build().map_err(|e| e.to_string())?;
+    if game.reels().len() != NUM_REELS {
+        return Err(format!(
+            "The legacy evaluator supports only {} reels!",
+            NUM_REELS
+        ));
+    }
+
+    let symbols = SymbolSet::default();
+    let mismatches = differential(game.paytable(), game.reels(), game.paylines(), seed, spins);
+    for x in &mismatches {
+        println!(
+            "seed {} line {}: legacy {}, paytable {}, lookup {:?}\n{}",
+            x.seed,
+            x.line,
+            x.legacy,
+            x.paytable,
+            x.lookup,
+            symbols.render(&x.window)
+        );
+    }
+
+    println!("Windows:    {}", spins);
+    println!("Mismatches: {}", mismatches.len());
+
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "The paytable differs from the legacy evaluator (seed {})",
+            seed
+        ))
+    }
+}
+
+// A provably fair spin recomputed from the revealed seeds.
+struct Replay {
+    server_seed: String,
+    client_seed: String,
+    nonce: u64,
+    commitment: String,
+    stops: Vec<usize>,
+    game: Game,
+    result: SpinResult,
+}
+
+// Recomputes the provably fair spin of the options,
+// fails if the server seed does not match the `--commitment`.
+fn replay(options: Options) -> Result<Replay, String> {
+    let required = |option| format!("`{}` is required!\n{}", option, USAGE);
+    let server_seed = options
+        .server_seed
+        .ok_or_else(|| required("--server-seed"))?;
+    let client_seed = options
+        .client_seed
+        .ok_or_else(|| required("--client-seed"))?;
+    let nonce = options.nonce.ok_or_else(|| required("--nonce"))?;
+
+    let commitment = fairness::commitment(&server_seed);
+    if let Some(expected) = options.commitment.filter(|x| *x != commitment) {
+        return Err(format!(
+            "The server seed does not match the commitment {}, its hash is {}",
+            expected, commitment
+        ));
+    }
+
+    // The credits only have to cover the bet, the window depends on the seeds alone.
+    let game = options
+        .config
+        .credits(u32::MAX / 2)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let result = game
+        .replay_fair(&server_seed, &client_seed, nonce)
+        .map_err(|e| e.to_string())?;
+    let stops = fairness::stops(&server_seed, &client_seed, nonce, game.reels());
+
+    Ok(Replay {
+        server_seed,
+        client_seed,
+        nonce,
+        commitment,
+        stops,
+        game,
+        result,
+    })
+}
+
+// Recomputes the provably fair spin and prints its outcome.
+fn run_verify(options: Options) -> Result<(), String> {
+    let replay = replay(options)?;
+    let result = &replay.result;
+
+    println!("Commitment: {}", replay.commitment);
+    println!("Stops:      {:?}", replay.stops);
+    println!("{}", SymbolSet::default().render(&result.window));
+    for line in &result.lines {
+        println!(
+            "Line {}: {}, win {}",
+            line.line + 1,
+            line.combination,
+            line.win
+        );
+    }
+    if let Some(scatter) = &result.scatter {
+        println!("Scatter: {}, win {}", scatter.combination, scatter.win);
+    }
+    println!("Win at bet {}: {}", result.bet, result.win);
+
+    Ok(())
+}
+
+// Recomputes the provably fair spin and prints a Markdown receipt of the round.
+// The round is identified by the commitment and the nonce, the machine by the version
+// of the crate and the SHA-256 hash of its paytable and reel strips.
+fn run_receipt(options: Options) -> Result<(), String> {
+    let replay = replay(options)?;
+    let result = &replay.result;
+    let config = serde_json::to_string(&(replay.game.paytable(), replay.game.reels()))
+        .map_err(|e| e.to_string())?;
+
+    println!("# Round receipt");
+    println!();
+    println!("| | |");
+    println!("|---|---|");
+    println!(
+        "| Machine | slot_machine {}, config `{:x}` |",
+        env!("CARGO_PKG_VERSION"),
+        Sha256::digest(config.as_bytes())
+    );
+    println!("| Round | {} of `{}` |", replay.nonce, replay.commitment);
+    println!("| Bet | {} |", result.bet);
+    println!("| Win | {} |", result.win);
+    println!();
+    println!("## Outcome");
+    println!();
+    println!("```");
+    println!("{}", SymbolSet::default().render(&result.window));
+    println!("```");
+    println!();
+    println!("| Combination | Win |");
+    println!("|---|---|");
+    for line in &result.lines {
+        println!(
+            "| Line {}: {} | {} |",
+            line.line + 1,
+            line.combination,
+            line.win
+        );
+    }
+    if let Some(scatter) = &result.scatter {
+        println!("| Scatter: {} | {} |", scatter.combination, scatter.win);
+    }
+    println!();
+    println!("## Fairness");
+    println!();
+    println!("- Commitment: `{}`", replay.commitment);
+    println!("- Server seed: `{}`", replay.server_seed);
+    println!("- Client seed: `{}`", replay.client_seed);
+    println!("- Nonce: {}", replay.nonce);
+    println!("- Stops: {:?}", replay.stops);
+
+    Ok(())
+}
+
+// Parses the `value` of the `option` as a number.
+fn parse_number(option: &str, value: &str) -> Result<u64, String> {
+    value
+        .parse()
+        .map_err(|_| format!("`{}` must be a number, got `{}`!", option, value))
+}
@@ -0,0 +1,24 @@
+//! The commonly used types, `use slot_machine::prelude::*;` is enough to play a game.
+//!
+//! # Examples
+//!
+//! ```
+//! use slot_machine::prelude::*;
+//!
+//! let mut game = GameBuilder::new().credits(100).build().unwrap();
+//!
+//! match game.spin() {
+//!     Ok(result) => println!("{:?}", result.window),
+//!     Err(GameError::LowBalance) => println!("Please insert credits"),
+//!     Err(e) => println!("{}", e),
+//! }
+//! ```
+
+pub use crate::game::builder::GameBuilder;
+pub use crate::game::error::GameError;
+pub use crate::game::paytable::Paytable;
+pub use crate::game::reel::ReelStrip;
+pub use crate::game::spin::SpinResult;
+pub use crate::game::symbol::Symbol;
+pub use crate::game::wallet::Wallet;
+pub use crate::game::Game;